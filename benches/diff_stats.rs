@@ -0,0 +1,90 @@
+//! Benchmark for diff statistics of a single huge commit
+//!
+//! Run with: `cargo bench --bench diff_stats`
+//!
+//! Builds a throwaway repository whose last commit touches thousands of files,
+//! so per-file line counting dominates the measurement.
+
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use git2::{IndexAddOption, Signature};
+use kodo::git::Repository;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Lines written to each file of the huge commit
+const LINES_PER_FILE: usize = 20;
+
+/// Stage everything in the working tree and commit it on HEAD
+fn commit_all(repo: &git2::Repository, message: &str) {
+    let mut index = repo.index().expect("open index");
+    index
+        .add_all(["*"], IndexAddOption::DEFAULT, None)
+        .expect("stage files");
+    index.write().expect("write index");
+    let tree = repo
+        .find_tree(index.write_tree().expect("write tree"))
+        .expect("find tree");
+
+    let signature = Signature::now("Bench", "bench@example.com").expect("signature");
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<_> = parent.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )
+    .expect("commit");
+}
+
+/// Create a repository whose HEAD commit rewrites `files` files
+fn create_huge_commit_repo(files: usize) -> TempDir {
+    let dir = TempDir::new().expect("create temp dir");
+    let repo = git2::Repository::init(dir.path()).expect("init repo");
+
+    let write_files = |path: &Path, version: &str| {
+        for i in 0..files {
+            let sub = path.join(format!("dir{}", i % 50));
+            fs::create_dir_all(&sub).expect("create dir");
+            let mut contents = String::new();
+            for line in 0..LINES_PER_FILE {
+                let _ = writeln!(contents, "{version} line {line} of file {i}");
+            }
+            fs::write(sub.join(format!("file{i}.txt")), contents).expect("write file");
+        }
+    };
+
+    write_files(dir.path(), "old");
+    commit_all(&repo, "Initial files");
+    write_files(dir.path(), "new");
+    commit_all(&repo, "Rewrite every file");
+
+    dir
+}
+
+/// Benchmark diff stats of one commit touching many files
+fn bench_huge_commit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("huge_commit");
+    group.sample_size(10);
+
+    for files in [1_000, 5_000] {
+        let dir = create_huge_commit_repo(files);
+        let repo = Repository::open(dir.path(), "huge-commit").expect("open repository");
+
+        group.bench_with_input(BenchmarkId::new("files", files), &files, |b, _| {
+            b.iter(|| {
+                repo.commits_in_revspec(black_box("HEAD~1..HEAD"), None, false)
+                    .expect("walk commits")
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_huge_commit);
+criterion_main!(benches);
@@ -10,8 +10,9 @@
 use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
 use kodo::cli::args::Period;
 use kodo::config::{default_config_path, expand_tilde, load_config};
-use kodo::git::Repository;
+use kodo::git::{Repository, Timezone};
 use kodo::stats::{DateRange, Days, collect_stats};
+use rayon::ThreadPoolBuilder;
 use std::env;
 use std::path::PathBuf;
 
@@ -84,12 +85,13 @@ fn bench_commits_in_range(c: &mut Criterion) {
 
     for days in [7, 30, 90] {
         group.bench_with_input(BenchmarkId::new("days", days), &days, |b, &days| {
-            let range = DateRange::last_n_days(Days::new(days));
+            let range = DateRange::last_n_days(Days::new(days), Timezone::Local);
             b.iter(|| {
                 repo.commits_in_range(
                     black_box(range.from),
                     black_box(range.to),
-                    None,
+                    &[],
+                    &[],
                     true, // exclude merges
                 )
             });
@@ -99,6 +101,46 @@ fn bench_commits_in_range(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark diff computation spread over different numbers of worker threads
+fn bench_parallel_diffs(c: &mut Criterion) {
+    let config = BenchmarkConfig::load();
+    let range = DateRange::last_n_days(Days::new(90), Timezone::Local);
+    let cpus = std::thread::available_parallelism().map_or(1, usize::from);
+
+    let mut group = c.benchmark_group("parallel_diffs");
+    group.sample_size(10);
+
+    let mut jobs_values = vec![1, 2, 4, cpus];
+    jobs_values.sort_unstable();
+    jobs_values.dedup();
+    for jobs in jobs_values {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .expect("Failed to build thread pool");
+
+        group.bench_with_input(BenchmarkId::new("jobs", jobs), &jobs, |b, &jobs| {
+            b.iter(|| {
+                // Repository handles are not `Sync`, so each run opens its own inside the pool
+                pool.install(|| {
+                    Repository::open(&config.repo_path, &config.repo_name)
+                        .expect("Failed to open repository")
+                        .with_parallel_diffs(jobs > 1)
+                        .commits_in_range(
+                            black_box(range.from),
+                            black_box(range.to),
+                            &[],
+                            &[],
+                            true,
+                        )
+                })
+            });
+        });
+    }
+
+    group.finish();
+}
+
 /// Benchmark `collect_stats` function
 fn bench_collect_stats(c: &mut Criterion) {
     let config = BenchmarkConfig::load();
@@ -112,10 +154,11 @@ fn bench_collect_stats(c: &mut Criterion) {
     });
 
     // Pre-fetch commits for 30 days
-    let range = DateRange::last_n_days(Days::new(30));
+    let range = DateRange::last_n_days(Days::new(30), Timezone::Local);
     let commits = repo
-        .commits_in_range(range.from, range.to, None, true)
-        .expect("Failed to fetch commits");
+        .commits_in_range(range.from, range.to, &[], &[], true)
+        .expect("Failed to fetch commits")
+        .commits;
 
     println!("Benchmarking collect_stats with {} commits", commits.len());
 
@@ -125,10 +168,10 @@ fn bench_collect_stats(c: &mut Criterion) {
         b.iter(|| {
             collect_stats(
                 black_box(&config.repo_name),
-                black_box(commits.clone()),
+                black_box(&commits),
                 black_box(range),
                 black_box(Period::Daily),
-                None,
+                Timezone::Local,
             )
         });
     });
@@ -137,10 +180,10 @@ fn bench_collect_stats(c: &mut Criterion) {
         b.iter(|| {
             collect_stats(
                 black_box(&config.repo_name),
-                black_box(commits.clone()),
+                black_box(&commits),
                 black_box(range),
                 black_box(Period::Weekly),
-                None,
+                Timezone::Local,
             )
         });
     });
@@ -148,5 +191,10 @@ fn bench_collect_stats(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_commits_in_range, bench_collect_stats);
+criterion_group!(
+    benches,
+    bench_commits_in_range,
+    bench_parallel_diffs,
+    bench_collect_stats
+);
 criterion_main!(benches);
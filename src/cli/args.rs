@@ -1,12 +1,45 @@
 //! Command-line argument definitions
 
-use clap::{Parser, Subcommand, ValueEnum};
+pub use crate::git::DateField;
+use crate::git::{DEFAULT_RENAME_THRESHOLD, DEFAULT_SKEW_SLOP, Timezone};
+pub use crate::output::OutputFormat;
+use crate::stats::period::parse_bucket;
+pub use crate::stats::{MetricGroup, Period, WeekLabel, WeekStart};
+use clap::builder::BoolishValueParser;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::path::PathBuf;
 
+/// Builder for on/off options that a config or env default can turn back off
+trait SwitchArg {
+    /// Take an optional value, so `--flag` turns the option on and `--flag=false` off
+    fn switch(self, on: bool) -> Self;
+}
+
+impl SwitchArg for Arg {
+    fn switch(self, on: bool) -> Self {
+        if !on {
+            return self;
+        }
+        self.num_args(0..=1)
+            .require_equals(true)
+            .default_value("false")
+            .default_missing_value("true")
+            .value_name("BOOL")
+            .value_parser(BoolishValueParser::new())
+            .action(ArgAction::Set)
+    }
+}
+
 /// Analyze Git commit statistics across repositories
 #[derive(Parser, Debug)]
 #[command(name = "kodo")]
 #[command(version, about, long_about = None)]
+// CLI flags are independent on/off switches
+#[allow(clippy::struct_excessive_bools)]
 pub struct Args {
     #[command(subcommand)]
     pub command: Option<Command>,
@@ -15,41 +48,328 @@ pub struct Args {
     #[arg(short, long, env = "KODO_CONFIG", global = true)]
     pub config: Option<PathBuf>,
 
+    /// Directory for cached diff statistics [default: the platform cache dir, e.g. ~/.cache/kodo]
+    #[arg(long, value_name = "DIR", env = "KODO_CACHE_DIR", global = true)]
+    pub cache_dir: Option<PathBuf>,
+
     /// Repository path (overrides config)
     #[arg(short, long)]
     pub repo: Option<PathBuf>,
 
-    /// Number of days to analyze
-    #[arg(short, long, default_value = "7")]
+    /// Number of days to analyze (ending at --to, or today)
+    #[arg(short, long, env = "KODO_DAYS", default_value = "7")]
     pub days: u32,
 
-    /// Include merge commits
-    #[arg(long)]
+    /// Start date: YYYY-MM-DD or an expression like "2 weeks ago", "last monday", "start of quarter"
+    #[arg(long, visible_alias = "since")]
+    pub from: Option<String>,
+
+    /// End date (inclusive), same formats as --from [default: today]
+    #[arg(long, visible_alias = "until")]
+    pub to: Option<String>,
+
+    /// Include merge commits (--include-merges=false turns a config or env default off)
+    #[arg(long, env = "KODO_INCLUDE_MERGES", switch = true)]
     pub include_merges: bool,
 
+    /// Follow only the first parent of merges; each merge counts as one change (implies --include-merges)
+    #[arg(long, env = "KODO_FIRST_PARENT", switch = true)]
+    pub first_parent: bool,
+
+    /// Stop walking after this many consecutive commits older than the range (0 walks all history)
+    #[arg(long, value_name = "COMMITS", env = "KODO_SKEW_SLOP", default_value_t = DEFAULT_SKEW_SLOP)]
+    pub skew_slop: u32,
+
     /// Output format
-    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    #[arg(short, long, value_enum, env = "KODO_OUTPUT", default_value_t = OutputFormat::Table)]
     pub output: OutputFormat,
 
+    /// Timezone for bucketing commits: local, utc, an IANA name (e.g. Asia/Tokyo) or author
+    #[arg(long, env = "KODO_TZ", default_value = "local")]
+    pub tz: Timezone,
+
+    /// Commit date used for date ranges and bucketing
+    #[arg(long, value_enum, env = "KODO_DATE_FIELD", default_value_t = DateField::Committer)]
+    pub date_field: DateField,
+
     /// Aggregation period
-    #[arg(short, long, value_enum, default_value = "daily")]
+    #[arg(short, long, value_enum, env = "KODO_PERIOD", default_value = "daily")]
     pub period: Period,
 
-    /// Branch to analyze
-    #[arg(short, long)]
-    pub branch: Option<String>,
+    /// First day of the week for weekly periods
+    #[arg(long, value_enum, env = "KODO_WEEK_START", default_value_t = WeekStart::Monday)]
+    pub week_start: WeekStart,
+
+    /// Label of weekly periods: ISO week (2026-W42), start date, or date range
+    #[arg(long, value_enum, env = "KODO_WEEK_LABEL", default_value_t = WeekLabel::Iso)]
+    pub week_label: WeekLabel,
+
+    /// Fixed-length periods instead of --period, e.g. 14d or 2w
+    #[arg(long, value_name = "LENGTH", env = "KODO_BUCKET", value_parser = parse_bucket)]
+    pub bucket: Option<u32>,
+
+    /// Date the first --bucket period starts on (YYYY-MM-DD or expression) [default: --from]
+    #[arg(long, value_name = "DATE", env = "KODO_BUCKET_START")]
+    pub bucket_start: Option<String>,
+
+    /// Metrics to report (comma-separated); diffs are skipped when only commits are selected
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        env = "KODO_METRICS",
+        default_value = "commits,lines,files,binary"
+    )]
+    pub metrics: Vec<MetricGroup>,
+
+    /// Branches to analyze (comma-separated or repeatable)
+    #[arg(short, long, value_delimiter = ',', env = "KODO_BRANCH")]
+    pub branch: Vec<String>,
+
+    /// Analyze all local branches
+    #[arg(long, env = "KODO_ALL_BRANCHES", switch = true)]
+    pub all_branches: bool,
+
+    /// Analyze all refs matching a glob (e.g. 'refs/remotes/origin/*', repeatable)
+    #[arg(long, value_name = "GLOB", value_delimiter = ',', env = "KODO_REFS")]
+    pub refs: Vec<String>,
+
+    /// Revision range to analyze instead of a date window (e.g. v1.2.0..v1.3.0, A...B)
+    // Conflicts with `--days` and branch options given on the command line are
+    // checked in `try_parse_layered_from`, so values from env or config are just ignored
+    #[arg(long)]
+    pub rev: Option<String>,
 
     /// File extensions to include (comma-separated)
-    #[arg(long, value_delimiter = ',')]
+    #[arg(long, value_delimiter = ',', env = "KODO_EXT")]
     pub ext: Option<Vec<String>>,
 
+    /// Only count files matching this gitignore-style glob (repeatable)
+    #[arg(long, value_name = "GLOB", value_delimiter = ',', env = "KODO_INCLUDE")]
+    pub include: Vec<String>,
+
+    /// Skip files matching this gitignore-style glob (repeatable)
+    #[arg(long, value_name = "GLOB", value_delimiter = ',', env = "KODO_EXCLUDE")]
+    pub exclude: Vec<String>,
+
+    /// Similarity threshold (0-100) for rename and copy detection
+    #[arg(long, env = "KODO_RENAME_THRESHOLD", default_value_t = DEFAULT_RENAME_THRESHOLD, value_parser = clap::value_parser!(u16).range(0..=100))]
+    pub rename_threshold: u16,
+
+    /// Disable rename and copy detection
+    #[arg(long, env = "KODO_NO_RENAMES", switch = true)]
+    pub no_renames: bool,
+
+    /// Only count commits and lines touching these paths (git pathspec, after --)
+    #[arg(last = true, value_name = "PATHSPEC")]
+    pub pathspec: Vec<String>,
+
+    /// Count files marked linguist-generated or linguist-vendored in .gitattributes
+    #[arg(long, env = "KODO_INCLUDE_GENERATED", switch = true)]
+    pub include_generated: bool,
+
+    /// Worker threads for computing diffs (0 uses one per CPU, 1 disables parallelism)
+    #[arg(short, long, value_name = "N", env = "KODO_JOBS", default_value_t = 0)]
+    pub jobs: usize,
+
+    /// Recompute every diff instead of using the on-disk cache
+    #[arg(long)]
+    pub no_cache: bool,
+
     /// Show single metric instead of all metrics (TUI mode)
     #[arg(long)]
     pub single_metric: bool,
 
+    /// Also analyze checked-out submodules, each as its own repository
+    #[arg(long, env = "KODO_RECURSE_SUBMODULES", switch = true)]
+    pub recurse_submodules: bool,
+
+    /// Stop at the first repository that fails instead of skipping it with a warning
+    #[arg(long, env = "KODO_STRICT", switch = true)]
+    pub strict: bool,
+
     /// Filter repositories by name (comma-separated, from config)
     #[arg(long, value_delimiter = ',')]
     pub repo_name: Option<Vec<String>>,
+
+    /// Filter commits by author name or email (substring or regex, repeatable)
+    #[arg(long, env = "KODO_AUTHOR", value_delimiter = ',')]
+    pub author: Vec<String>,
+
+    /// Include a per-author breakdown in the output
+    #[arg(long, env = "KODO_BY_AUTHOR", switch = true)]
+    pub by_author: bool,
+
+    /// Where each layered option's value came from (see [`Args::parse_layered`])
+    #[arg(skip)]
+    pub sources: HashMap<LayeredOption, Source>,
+}
+
+/// Define [`LayeredOption`] from `Variant => field` pairs
+macro_rules! layered_options {
+    ($($variant:ident => $field:ident),* $(,)?) => {
+        /// Options that can also be set by the config file
+        ///
+        /// Each option is named after its `Args` field, which is also its key in
+        /// the config `defaults` block.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[allow(missing_docs)]
+        pub enum LayeredOption {
+            $($variant),*
+        }
+
+        impl LayeredOption {
+            /// Every layered option, in `config show --resolved` order
+            pub const ALL: &[Self] = &[$(Self::$variant),*];
+
+            /// Name of the option's `Args` field
+            #[must_use]
+            pub const fn id(self) -> &'static str {
+                match self {
+                    $(Self::$variant => stringify!($field)),*
+                }
+            }
+        }
+    };
+}
+
+layered_options! {
+    Days => days,
+    Period => period,
+    WeekStart => week_start,
+    WeekLabel => week_label,
+    Bucket => bucket,
+    BucketStart => bucket_start,
+    Output => output,
+    Tz => tz,
+    DateField => date_field,
+    Metrics => metrics,
+    Branch => branch,
+    AllBranches => all_branches,
+    Refs => refs,
+    Ext => ext,
+    Include => include,
+    Exclude => exclude,
+    Author => author,
+    ByAuthor => by_author,
+    IncludeMerges => include_merges,
+    FirstParent => first_parent,
+    IncludeGenerated => include_generated,
+    RenameThreshold => rename_threshold,
+    NoRenames => no_renames,
+    SkewSlop => skew_slop,
+    Jobs => jobs,
+    RecurseSubmodules => recurse_submodules,
+    Strict => strict,
+}
+
+impl LayeredOption {
+    /// Environment variable that sets the option, as declared on its `Args` field
+    #[must_use]
+    pub fn env(self, command: &clap::Command) -> Option<String> {
+        command
+            .get_arguments()
+            .find(|arg| arg.get_id() == self.id())
+            .and_then(clap::Arg::get_env)
+            .map(|env| env.to_string_lossy().into_owned())
+    }
+}
+
+/// Where the effective value of an option came from, from lowest to highest precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Source {
+    /// Built-in default
+    Default,
+    /// `defaults` block of the config file
+    Config,
+    /// Override in the repository's config entry
+    Repository,
+    /// `KODO_*` environment variable
+    Env,
+    /// Command-line flag
+    Cli,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::Config => write!(f, "config"),
+            Self::Repository => write!(f, "repository"),
+            Self::Env => write!(f, "env"),
+            Self::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+impl Args {
+    /// Parse the process arguments, recording where each layered option came from
+    ///
+    /// Unlike [`Parser::parse`], this keeps config defaults from replacing options
+    /// given on the command line or through a `KODO_*` variable.
+    #[must_use]
+    pub fn parse_layered() -> Self {
+        Self::parse_layered_from(std::env::args_os())
+    }
+
+    /// Parse the given arguments like [`Args::parse_layered`]
+    #[must_use]
+    pub fn parse_layered_from<I, T>(itr: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        Self::try_parse_layered_from(itr).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Parse the given arguments like [`Args::parse_layered`], returning errors
+    ///
+    /// # Errors
+    /// Returns a clap error for invalid arguments, or when `--rev` is combined
+    /// with `--days`, `--branch`, `--all-branches` or `--refs` on the command line.
+    pub fn try_parse_layered_from<I, T>(itr: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut command = Self::command();
+        let matches = command.try_get_matches_from_mut(itr)?;
+        let mut args = Self::from_arg_matches(&matches)?;
+
+        if matches.value_source("rev") == Some(ValueSource::CommandLine)
+            && let Some(flag) = ["days", "branch", "all_branches", "refs"]
+                .into_iter()
+                .find(|id| matches.value_source(id) == Some(ValueSource::CommandLine))
+        {
+            let flag = flag.replace('_', "-");
+            return Err(command.error(
+                ErrorKind::ArgumentConflict,
+                format!("the argument '--rev' cannot be used with '--{flag}'"),
+            ));
+        }
+
+        for &option in LayeredOption::ALL {
+            let source = match matches.value_source(option.id()) {
+                Some(ValueSource::CommandLine) => Source::Cli,
+                Some(ValueSource::EnvVariable) => Source::Env,
+                _ => Source::Default,
+            };
+            args.sources.insert(option, source);
+        }
+        Ok(args)
+    }
+
+    /// Where an option's value came from
+    ///
+    /// Options without a recorded source (e.g. when parsed with [`Parser::parse`])
+    /// count as built-in defaults, so config defaults apply to them as well.
+    #[must_use]
+    pub fn source(&self, option: LayeredOption) -> Source {
+        self.sources
+            .get(&option)
+            .copied()
+            .unwrap_or(Source::Default)
+    }
 }
 
 /// Available subcommands
@@ -61,6 +381,10 @@ pub enum Command {
     Remove(RemoveArgs),
     /// List registered repositories
     List(ListArgs),
+    /// Inspect or clear the diff cache
+    Cache(CacheArgs),
+    /// Show the configuration
+    Config(ConfigArgs),
 }
 
 /// Arguments for the `add` subcommand
@@ -93,75 +417,137 @@ pub struct ListArgs {
     pub json: bool,
 }
 
-/// Output format options
-#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub enum OutputFormat {
-    /// Terminal UI with charts
-    Tui,
-    /// Table output
-    #[default]
-    Table,
-    /// JSON output
-    Json,
-    /// CSV output
-    Csv,
+/// Arguments for the `cache` subcommand
+#[derive(Parser, Debug)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub action: CacheAction,
 }
 
-impl std::fmt::Display for OutputFormat {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Tui => write!(f, "tui"),
-            Self::Table => write!(f, "table"),
-            Self::Json => write!(f, "json"),
-            Self::Csv => write!(f, "csv"),
-        }
-    }
+/// Actions of the `cache` subcommand
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheAction {
+    /// Show where the cache is and how much it holds
+    Stats,
+    /// Delete all cached diff statistics
+    Clear,
 }
 
-/// Time period for aggregation
-#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub enum Period {
-    /// Aggregate by day
-    #[default]
-    Daily,
-    /// Aggregate by week
-    Weekly,
-    /// Aggregate by month
-    Monthly,
-    /// Aggregate by year
-    Yearly,
+/// Arguments for the `config` subcommand
+#[derive(Parser, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub action: ConfigAction,
 }
 
-impl std::fmt::Display for Period {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Daily => write!(f, "daily"),
-            Self::Weekly => write!(f, "weekly"),
-            Self::Monthly => write!(f, "monthly"),
-            Self::Yearly => write!(f, "yearly"),
-        }
-    }
+/// Actions of the `config` subcommand
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigAction {
+    /// Print the config file, or with --resolved the effective analysis options
+    Show {
+        /// Show each effective option and the layer it came from
+        #[arg(long)]
+        resolved: bool,
+    },
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use clap::CommandFactory;
 
     #[test]
-    fn test_output_format_display() {
-        assert_eq!(OutputFormat::Tui.to_string(), "tui");
-        assert_eq!(OutputFormat::Table.to_string(), "table");
-        assert_eq!(OutputFormat::Json.to_string(), "json");
-        assert_eq!(OutputFormat::Csv.to_string(), "csv");
+    fn test_every_analysis_option_is_layered() {
+        // Options that pick the config, cache or repositories, or only apply to one run
+        const NOT_LAYERED: [&str; 12] = [
+            "config",
+            "cache_dir",
+            "repo",
+            "repo_name",
+            "from",
+            "to",
+            "rev",
+            "pathspec",
+            "no_cache",
+            "single_metric",
+            "help",
+            "version",
+        ];
+
+        let command = Args::command();
+        for arg in command.get_arguments() {
+            let id = arg.get_id().as_str();
+            let layered = LayeredOption::ALL.iter().any(|option| option.id() == id);
+            assert!(
+                layered != NOT_LAYERED.contains(&id),
+                "--{id} must be either a LayeredOption or listed as not layered"
+            );
+        }
+        for option in LayeredOption::ALL {
+            let env = format!("KODO_{}", option.id().to_uppercase());
+            assert_eq!(option.env(&command), Some(env), "{option:?}");
+        }
     }
 
     #[test]
-    fn test_period_display() {
-        assert_eq!(Period::Daily.to_string(), "daily");
-        assert_eq!(Period::Weekly.to_string(), "weekly");
-        assert_eq!(Period::Monthly.to_string(), "monthly");
-        assert_eq!(Period::Yearly.to_string(), "yearly");
+    fn test_switches_take_an_optional_bool() {
+        let switches = [
+            "include_merges",
+            "first_parent",
+            "all_branches",
+            "no_renames",
+            "include_generated",
+            "recurse_submodules",
+            "strict",
+            "by_author",
+        ];
+        for id in switches {
+            let flag = format!("--{}", id.replace('_', "-"));
+            for (arg, expected) in [
+                (flag.clone(), true),
+                (format!("{flag}=false"), false),
+                (format!("{flag}=yes"), true),
+            ] {
+                let matches = Args::command().get_matches_from(["kodo", arg.as_str()]);
+                assert_eq!(matches.get_one::<bool>(id), Some(&expected), "{arg}");
+            }
+            let matches = Args::command().get_matches_from(["kodo"]);
+            assert_eq!(matches.get_one::<bool>(id), Some(&false), "{id}");
+        }
+    }
+
+    #[test]
+    fn test_week_options() {
+        let args = Args::parse_from(["kodo"]);
+        assert_eq!(args.week_start, WeekStart::Monday);
+        assert_eq!(args.week_label, WeekLabel::Iso);
+
+        let args = Args::parse_from([
+            "kodo",
+            "--week-start",
+            "sunday",
+            "--week-label",
+            "start-date",
+        ]);
+        assert_eq!(args.week_start, WeekStart::Sunday);
+        assert_eq!(args.week_label, WeekLabel::StartDate);
+        assert_eq!(args.week_label.to_string(), "start-date");
+        assert!(Args::try_parse_from(["kodo", "--week-start", "friday"]).is_err());
+    }
+
+    #[test]
+    fn test_bucket_option() {
+        let args = Args::parse_from(["kodo", "--bucket", "14d", "--bucket-start", "2026-10-05"]);
+        assert_eq!(args.bucket, Some(14));
+        assert_eq!(args.bucket_start.as_deref(), Some("2026-10-05"));
+        assert_eq!(
+            Args::parse_from(["kodo", "--bucket", "2w"]).bucket,
+            Some(14)
+        );
+        assert_eq!(Args::parse_from(["kodo"]).bucket, None);
+
+        for invalid in ["14", "0d", "d", "-1w", "2 weeks"] {
+            assert!(Args::try_parse_from(["kodo", "--bucket", invalid]).is_err());
+        }
     }
 
     #[test]
@@ -186,6 +572,61 @@ mod tests {
         assert_eq!(args.days, 30);
     }
 
+    #[test]
+    fn test_args_with_date_range() {
+        let args = Args::parse_from(["kodo", "--from", "2026-01-01", "--to", "start of quarter"]);
+        assert_eq!(args.from.as_deref(), Some("2026-01-01"));
+        assert_eq!(args.to.as_deref(), Some("start of quarter"));
+
+        let args = Args::parse_from(["kodo", "--since", "2 weeks ago", "--until", "yesterday"]);
+        assert_eq!(args.from.as_deref(), Some("2 weeks ago"));
+        assert_eq!(args.to.as_deref(), Some("yesterday"));
+    }
+
+    #[test]
+    fn test_args_with_rev() {
+        let args = Args::parse_from(["kodo", "--rev", "v1.2.0..v1.3.0"]);
+        assert_eq!(args.rev.as_deref(), Some("v1.2.0..v1.3.0"));
+
+        let result = Args::try_parse_layered_from(["kodo", "--rev", "HEAD", "--branch", "main"]);
+        assert!(result.is_err());
+        let result = Args::try_parse_layered_from(["kodo", "--rev", "HEAD", "--all-branches"]);
+        assert!(result.is_err());
+        let result =
+            Args::try_parse_layered_from(["kodo", "--rev", "HEAD", "--all-branches=false"]);
+        assert!(result.is_err());
+        let result = Args::try_parse_layered_from(["kodo", "--rev", "HEAD", "--refs", "refs/*"]);
+        assert!(result.is_err());
+        let result = Args::try_parse_layered_from(["kodo", "--rev", "HEAD", "--days", "30"]);
+        assert!(result.is_err());
+        // --from/--to still narrow a revision range
+        let result =
+            Args::try_parse_layered_from(["kodo", "--rev", "HEAD", "--from", "2026-01-01"]);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_args_multiple_branches_and_refs() {
+        let args = Args::parse_from(["kodo"]);
+        assert!(args.branch.is_empty());
+        assert!(!args.all_branches);
+        assert!(args.refs.is_empty());
+
+        let args = Args::parse_from([
+            "kodo",
+            "--branch",
+            "main,develop",
+            "-b",
+            "release",
+            "--refs",
+            "refs/remotes/origin/*",
+            "--all-branches",
+        ]);
+        assert_eq!(args.branch, vec!["main", "develop", "release"]);
+        assert_eq!(args.refs, vec!["refs/remotes/origin/*"]);
+        assert!(args.all_branches);
+    }
+
     #[test]
     fn test_args_output_tui_explicit() {
         let args = Args::parse_from(["kodo", "--output", "tui"]);
@@ -219,6 +660,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_args_with_authors() {
+        let args = Args::parse_from(["kodo", "--author", "alice", "--author", "@corp\\.com"]);
+        assert_eq!(
+            args.author,
+            vec!["alice".to_string(), "@corp\\.com".to_string()]
+        );
+        assert!(!args.by_author);
+
+        let args = Args::parse_from(["kodo", "--by-author"]);
+        assert!(args.author.is_empty());
+        assert!(args.by_author);
+    }
+
+    #[test]
+    fn test_args_rename_detection() {
+        let args = Args::parse_from(["kodo"]);
+        assert_eq!(args.rename_threshold, DEFAULT_RENAME_THRESHOLD);
+        assert!(!args.no_renames);
+
+        let args = Args::parse_from(["kodo", "--rename-threshold", "80", "--no-renames"]);
+        assert_eq!(args.rename_threshold, 80);
+        assert!(args.no_renames);
+
+        assert!(Args::try_parse_from(["kodo", "--rename-threshold", "101"]).is_err());
+    }
+
+    #[test]
+    fn test_args_tz() {
+        assert_eq!(Args::parse_from(["kodo"]).tz, Timezone::Local);
+        assert_eq!(
+            Args::parse_from(["kodo", "--tz", "author"]).tz,
+            Timezone::Author
+        );
+        assert_eq!(
+            Args::parse_from(["kodo", "--tz", "Asia/Tokyo"]).tz,
+            Timezone::Named(chrono_tz::Asia::Tokyo)
+        );
+        assert!(Args::try_parse_from(["kodo", "--tz", "Nowhere/Special"]).is_err());
+    }
+
+    #[test]
+    fn test_args_date_field() {
+        assert_eq!(Args::parse_from(["kodo"]).date_field, DateField::Committer);
+        assert_eq!(
+            Args::parse_from(["kodo", "--date-field", "author"]).date_field,
+            DateField::Author
+        );
+        assert_eq!(DateField::Author.to_string(), "author");
+        assert!(Args::try_parse_from(["kodo", "--date-field", "tagger"]).is_err());
+    }
+
+    #[test]
+    fn test_args_skew_slop() {
+        assert_eq!(Args::parse_from(["kodo"]).skew_slop, DEFAULT_SKEW_SLOP);
+        assert_eq!(Args::parse_from(["kodo", "--skew-slop", "0"]).skew_slop, 0);
+    }
+
+    #[test]
+    fn test_args_first_parent() {
+        assert!(!Args::parse_from(["kodo"]).first_parent);
+        assert!(Args::parse_from(["kodo", "--first-parent"]).first_parent);
+        assert!(Args::parse_from(["kodo", "--first-parent=true"]).first_parent);
+        assert!(!Args::parse_from(["kodo", "--first-parent=false"]).first_parent);
+        assert!(Args::try_parse_from(["kodo", "--first-parent=maybe"]).is_err());
+    }
+
+    #[test]
+    fn test_args_path_filters() {
+        let args = Args::parse_from(["kodo"]);
+        assert!(args.include.is_empty());
+        assert!(args.exclude.is_empty());
+        assert!(!args.include_generated);
+
+        let args = Args::parse_from([
+            "kodo",
+            "--include",
+            "src/",
+            "--exclude",
+            "*.lock",
+            "--exclude",
+            "vendor/",
+            "--include-generated",
+        ]);
+        assert_eq!(args.include, vec!["src/"]);
+        assert_eq!(args.exclude, vec!["*.lock", "vendor/"]);
+        assert!(args.include_generated);
+    }
+
     #[test]
     fn test_add_command() {
         let args = Args::parse_from(["kodo", "add", "."]);
@@ -266,6 +796,87 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cache_command() {
+        let args = Args::parse_from(["kodo", "cache", "stats"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::Cache(CacheArgs {
+                action: CacheAction::Stats
+            }))
+        ));
+
+        let args = Args::parse_from(["kodo", "cache", "clear", "--cache-dir", "/tmp/kodo"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::Cache(CacheArgs {
+                action: CacheAction::Clear
+            }))
+        ));
+        assert_eq!(args.cache_dir, Some(PathBuf::from("/tmp/kodo")));
+    }
+
+    #[test]
+    fn test_config_show_command() {
+        let args = Args::parse_from(["kodo", "config", "show"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::Config(ConfigArgs {
+                action: ConfigAction::Show { resolved: false }
+            }))
+        ));
+
+        let args = Args::parse_from(["kodo", "-p", "monthly", "config", "show", "--resolved"]);
+        assert!(matches!(
+            args.command,
+            Some(Command::Config(ConfigArgs {
+                action: ConfigAction::Show { resolved: true }
+            }))
+        ));
+        assert_eq!(args.period, Period::Monthly);
+    }
+
+    #[test]
+    fn test_jobs_option() {
+        assert_eq!(Args::parse_from(["kodo"]).jobs, 0);
+        assert_eq!(Args::parse_from(["kodo", "-j", "4"]).jobs, 4);
+        assert_eq!(Args::parse_from(["kodo", "--jobs", "1"]).jobs, 1);
+    }
+
+    #[test]
+    fn test_metrics_option() {
+        let args = Args::parse_from(["kodo"]);
+        assert_eq!(args.metrics, MetricGroup::ALL);
+
+        let args = Args::parse_from(["kodo", "--metrics", "commits"]);
+        assert_eq!(args.metrics, vec![MetricGroup::Commits]);
+        assert!(!args.metrics.iter().any(|m| m.needs_diff()));
+
+        let args = Args::parse_from(["kodo", "--metrics", "commits,lines"]);
+        assert_eq!(args.metrics, vec![MetricGroup::Commits, MetricGroup::Lines]);
+    }
+
+    #[test]
+    fn test_pathspec_after_double_dash() {
+        assert!(Args::parse_from(["kodo"]).pathspec.is_empty());
+
+        let args = Args::parse_from(["kodo", "-d", "30", "--", "services/api", "*.proto"]);
+        assert_eq!(args.days, 30);
+        assert_eq!(args.pathspec, vec!["services/api", "*.proto"]);
+    }
+
+    #[test]
+    fn test_no_cache_flag() {
+        assert!(!Args::parse_from(["kodo"]).no_cache);
+        assert!(Args::parse_from(["kodo", "--no-cache"]).no_cache);
+    }
+
+    #[test]
+    fn test_strict_flag() {
+        assert!(!Args::parse_from(["kodo"]).strict);
+        assert!(Args::parse_from(["kodo", "--strict"]).strict);
+    }
+
     #[test]
     fn test_help_includes_output_short() {
         let help = Args::command().render_help().to_string();
@@ -2,6 +2,7 @@
 
 pub mod args;
 pub mod run;
+pub mod settings;
 
 pub use args::{AddArgs, Args, Command};
 pub use run::execute;
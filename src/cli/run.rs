@@ -1,16 +1,27 @@
 //! CLI execution logic
 
-use crate::cli::args::{AddArgs, Args, Command, ListArgs, OutputFormat, RemoveArgs};
+use crate::cli::args::{
+    AddArgs, Args, CacheAction, CacheArgs, Command, ConfigAction, ConfigArgs, LayeredOption,
+    ListArgs, OutputFormat, RemoveArgs,
+};
+use crate::cli::settings::{RepoOverrides, RepoSettings, apply_config_defaults, resolved_options};
 use crate::config::{
     Config, Defaults, RepoConfig, default_config_path, default_config_path_for_save, expand_tilde,
     load_config, save_config,
 };
-use crate::error::{Error, Result};
-use crate::git::{CommitInfo, Repository};
+use crate::error::{Error, RepoWarning, Result};
+use crate::git::{
+    CommitInfo, CommitWalk, DiffConfig, Repository, cache_stats, clear_cache, default_cache_dir,
+};
 use crate::output::{CsvFormatter, Formatter, JsonFormatter, TableFormatter};
-use crate::stats::{DateRange, Days, collect_activity_stats, collect_stats};
+use crate::stats::{
+    AuthorFilter, DateRange, Grouping, PathFilter, collect_activity_stats, collect_author_stats,
+    collect_repository_stats, collect_stats, parse_date,
+};
 use crate::tui::App;
+use chrono::NaiveDate;
 use indicatif::{ProgressBar, ProgressStyle};
+use rayon::ThreadPoolBuilder;
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -19,9 +30,27 @@ use std::time::Duration;
 struct RepoInfo {
     path: PathBuf,
     name: String,
-    branch: Option<String>,
+    /// Walk options set in the repository's config entry
+    overrides: RepoOverrides,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    /// Git pathspec limiting the commits and lines counted
+    pathspec: Vec<String>,
+}
+
+impl RepoInfo {
+    /// Combine the repository's path filters with those on the command line
+    fn with_cli_filters(mut self, args: &Args) -> Self {
+        self.include = [args.include.as_slice(), &self.include].concat();
+        self.exclude = [args.exclude.as_slice(), &self.exclude].concat();
+        self.pathspec.clone_from(&args.pathspec);
+        self
+    }
 }
 
+/// Commit walk of one repository, paired with the repository name
+type RepoWalk = (String, CommitWalk);
+
 /// RAII guard for spinner to ensure cleanup on error
 struct SpinnerGuard(Option<ProgressBar>);
 
@@ -72,13 +101,15 @@ impl Drop for SpinnerGuard {
 /// Panics if the progress bar style template is invalid (should never happen).
 // Takes ownership because args.command is consumed by match
 #[allow(clippy::needless_pass_by_value)]
-pub fn execute(args: Args) -> Result<()> {
+pub fn execute(mut args: Args) -> Result<()> {
     // Handle subcommands
-    if let Some(command) = args.command {
+    if let Some(command) = args.command.take() {
         return match command {
             Command::Add(add_args) => execute_add(add_args, args.config),
             Command::Remove(remove_args) => execute_remove(remove_args, args.config),
             Command::List(list_args) => execute_list(list_args, args.config),
+            Command::Cache(cache_args) => execute_cache(cache_args, args.cache_dir),
+            Command::Config(config_args) => execute_config(&config_args, args),
         };
     }
 
@@ -87,49 +118,151 @@ pub fn execute(args: Args) -> Result<()> {
     let spinner = SpinnerGuard::new(true);
 
     // Get repositories to analyze
-    let repos = get_repositories(&args)?;
+    let config = load_optional_config(&args)?;
+    if let Some(config) = &config {
+        apply_config_defaults(&mut args, &config.defaults);
+    }
+    let repos = get_repositories(&args, config.as_ref())?
+        .into_iter()
+        .map(|repo_info| repo_info.with_cli_filters(&args))
+        .collect();
+    let (repos, mut warnings) = expand_submodules(repos, &args)?;
+    let global_mailmap = match &config {
+        Some(config) => read_global_mailmap(config)?,
+        None => None,
+    };
 
-    // Calculate date range
-    let range = DateRange::last_n_days(Days::new(args.days));
-    let exclude_merges = !args.include_merges;
+    // Calculate date range. With --rev, dates only filter when given explicitly.
+    let range = resolve_date_range(&args, args.tz.today())?;
+    let grouping = resolve_grouping(&args, args.tz.today())?;
+    let explicit_dates = args.from.is_some() || args.to.is_some();
+    let date_window = (args.rev.is_none() || explicit_dates).then_some((range.from, range.to));
+    let author_filter = AuthorFilter::new(&args.author)?;
+    let mut ref_globs = args.refs.clone();
+    if args.all_branches {
+        ref_globs.push("refs/heads/*".to_string());
+    }
+    let diff_config = DiffConfig {
+        rename_threshold: (!args.no_renames).then_some(args.rename_threshold),
+        include_generated: args.include_generated,
+        // Set per repository, as submodules see the pathspec re-rooted
+        pathspec: Vec::new(),
+    };
+    let cache_dir = if args.no_cache {
+        None
+    } else {
+        args.cache_dir.clone().or_else(default_cache_dir)
+    };
 
-    // Collect commits from all repositories (parallel)
+    // Collect commits from all repositories (parallel, diffs share the same pool)
     spinner.set_message("Collecting commits...");
 
-    let results: Result<Vec<(String, Vec<CommitInfo>)>> = repos
-        .par_iter()
-        .map(|repo_info| {
-            let repo = Repository::open(&repo_info.path, &repo_info.name)?;
-            let branch = args.branch.as_deref().or(repo_info.branch.as_deref());
-            let commits = repo.commits_in_range(range.from, range.to, branch, exclude_merges)?;
-            Ok((repo_info.name.clone(), commits))
+    let collect_repo = |repo_info: &RepoInfo| -> Result<CommitWalk> {
+        let settings = RepoSettings::resolve(&args, &repo_info.overrides);
+        // First-parent mode treats each merge as the unit of change, so merges are kept
+        let exclude_merges = settings.exclude_merges();
+        let mut repo = Repository::open(&repo_info.path, &repo_info.name)?
+            .with_diff_config(DiffConfig {
+                pathspec: repo_info.pathspec.clone(),
+                ..diff_config.clone()
+            })
+            .with_first_parent(settings.first_parent.value)
+            .with_timezone(args.tz)
+            .with_date_field(args.date_field)
+            .with_skew_slop(args.skew_slop)
+            .with_parallel_diffs(args.jobs != 1)
+            .with_diffs(args.metrics.iter().any(|m| m.needs_diff()));
+        if let Some(dir) = &cache_dir {
+            repo = repo.with_cache_dir(dir.clone());
+        }
+        if let Some(mailmap) = &global_mailmap {
+            repo = repo.with_global_mailmap(mailmap)?;
+        }
+        let mut walk = if let Some(spec) = &args.rev {
+            repo.commits_in_revspec(spec, date_window, exclude_merges)?
+        } else {
+            let branches = &settings.branch.value;
+            repo.commits_in_range(range.from, range.to, branches, &ref_globs, exclude_merges)?
+        };
+        let extensions = settings.ext.value.as_deref();
+        apply_path_filter(&mut walk.commits, repo_info, extensions)?;
+        Ok(walk)
+    };
+
+    let pool = ThreadPoolBuilder::new().num_threads(args.jobs).build()?;
+    let results: Vec<(String, Result<CommitWalk>)> = pool.install(|| {
+        repos
+            .par_iter()
+            .map(|repo_info| (repo_info.name.clone(), collect_repo(repo_info)))
+            .collect()
+    });
+    let (walks, walk_warnings) = split_failures(results, args.strict)?;
+    warnings.extend(walk_warnings);
+
+    let mut out_of_range_commits = 0;
+    let repo_commits: Vec<(String, Vec<CommitInfo>)> = walks
+        .into_iter()
+        .map(|(name, walk)| {
+            out_of_range_commits += walk.out_of_range;
+            if let Some(e) = walk.cache_error {
+                warnings.push(RepoWarning::partial(name.clone(), e));
+            }
+            let commits = walk.commits.into_iter();
+            (name, commits.filter(|c| author_filter.matches(c)).collect())
         })
         .collect();
 
-    let results = results?;
-    let mut all_commits: Vec<CommitInfo> = Vec::new();
-    let mut repo_names: Vec<String> = Vec::new();
-    for (name, commits) in results {
-        all_commits.extend(commits);
-        repo_names.push(name);
-    }
-
-    // Create combined repository name
-    let combined_name = repo_names
-        .first()
-        .filter(|_| repo_names.len() == 1)
-        .cloned()
-        .unwrap_or_else(|| format!("{} repos", repo_names.len()));
+    // Without a date window, bucket over the dates the selected commits span
+    let range = if date_window.is_some() {
+        range
+    } else {
+        let dates = repo_commits.iter().flat_map(|(_, commits)| commits);
+        DateRange::spanning(dates.map(|c| c.date(args.tz))).unwrap_or(range)
+    };
 
     // Collect statistics
     spinner.set_message("Calculating statistics...");
-    let extensions = args.ext.as_deref();
-    let activity_stats = collect_activity_stats(&all_commits);
-    let result = collect_stats(&combined_name, all_commits, range, args.period, extensions);
+    let repositories = if repo_commits.len() > 1 {
+        repo_commits
+            .iter()
+            .map(|(name, commits)| {
+                collect_repository_stats(name, commits, range, grouping, args.tz)
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Create combined repository name
+    let combined_name = match repo_commits.as_slice() {
+        [(name, _)] => name.clone(),
+        repos => format!("{} repos", repos.len()),
+    };
+    let all_commits: Vec<CommitInfo> = repo_commits
+        .into_iter()
+        .flat_map(|(_, commits)| commits)
+        .collect();
+
+    let activity_stats = collect_activity_stats(&all_commits, args.tz);
+    let authors = if args.by_author {
+        collect_author_stats(&all_commits)
+    } else {
+        Vec::new()
+    };
+    let mut result = collect_stats(&combined_name, &all_commits, range, grouping, args.tz);
+    result.authors = authors;
+    result.repositories = repositories;
+    result.out_of_range_commits = out_of_range_commits;
+    result.warnings = warnings;
+    result.metrics.clone_from(&args.metrics);
 
     // Spinner is automatically cleared by Drop when going out of scope or on error
     drop(spinner);
 
+    for warning in &result.warnings {
+        eprintln!("warning: {warning}");
+    }
+
     // Format and output
     match args.output {
         OutputFormat::Table => {
@@ -156,8 +289,123 @@ pub fn execute(args: Args) -> Result<()> {
     Ok(())
 }
 
+/// Separate the repositories that were analyzed from those that failed
+///
+/// Failures become warnings so the remaining repositories are still reported.
+/// With `strict`, or when no repository could be analyzed, the first error is
+/// returned instead.
+fn split_failures(
+    results: Vec<(String, Result<CommitWalk>)>,
+    strict: bool,
+) -> Result<(Vec<RepoWalk>, Vec<RepoWarning>)> {
+    let mut walks = Vec::with_capacity(results.len());
+    let mut failures = Vec::new();
+    for (name, result) in results {
+        match result {
+            Ok(walk) => walks.push((name, walk)),
+            Err(err) if strict => return Err(err),
+            Err(err) => failures.push((name, err)),
+        }
+    }
+
+    if walks.is_empty() && !failures.is_empty() {
+        return Err(failures.swap_remove(0).1);
+    }
+
+    let warnings = failures
+        .into_iter()
+        .map(|(name, err)| RepoWarning::new(name, err))
+        .collect();
+    Ok((walks, warnings))
+}
+
+/// Drop changed files outside the repository's include/exclude globs and the extension list
+fn apply_path_filter(
+    commits: &mut [CommitInfo],
+    repo_info: &RepoInfo,
+    extensions: Option<&[String]>,
+) -> Result<()> {
+    let filter = PathFilter::new(&repo_info.include, &repo_info.exclude)?;
+
+    if !filter.is_empty() || extensions.is_some() {
+        let extensions = extensions.unwrap_or_default();
+        for commit in commits {
+            commit.diff.retain_files(|file| {
+                filter.matches(&file.path) && file.matches_extensions(extensions)
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve the analysis date range from `--from` / `--to` / `--days`
+///
+/// `--to` defaults to today. Without `--from`, the range covers `--days` days ending at `--to`.
+fn resolve_date_range(args: &Args, today: NaiveDate) -> Result<DateRange> {
+    let to = match &args.to {
+        Some(expr) => parse_date(expr, today, args.week_start)?,
+        None => today,
+    };
+    let from = match &args.from {
+        Some(expr) => parse_date(expr, today, args.week_start)?,
+        None => to - chrono::Duration::days(i64::from(args.days)),
+    };
+
+    if from > to {
+        return Err(Error::InvalidDateRange { from, to });
+    }
+
+    Ok(DateRange::new(from, to))
+}
+
+/// Resolve how days are grouped: `--bucket` lengths take precedence over `--period`
+fn resolve_grouping(args: &Args, today: NaiveDate) -> Result<Grouping> {
+    // A bucket from a lower layer (e.g. config) must not override --period on the CLI
+    let bucket = args
+        .bucket
+        .filter(|_| args.source(LayeredOption::Bucket) >= args.source(LayeredOption::Period));
+    let Some(days) = bucket else {
+        return Ok(Grouping::Calendar {
+            period: args.period,
+            week_start: args.week_start,
+            week_label: args.week_label,
+        });
+    };
+    let start = match &args.bucket_start {
+        Some(expr) => Some(parse_date(expr, today, args.week_start)?),
+        None => None,
+    };
+
+    Ok(Grouping::Buckets { days, start })
+}
+
+/// Load the config file if one is specified or exists at the default location
+fn load_optional_config(args: &Args) -> Result<Option<Config>> {
+    match args.config.clone().or_else(default_config_path) {
+        Some(path) if path.exists() => load_config(&path).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Read the global mailmap file referenced by the config, if any
+fn read_global_mailmap(config: &Config) -> Result<Option<String>> {
+    let Some(path) = &config.mailmap else {
+        return Ok(None);
+    };
+
+    let expanded = expand_tilde(path);
+    if !expanded.exists() {
+        return Err(Error::ConfigInvalid {
+            message: format!("Mailmap file not found: {}", expanded.display()),
+        });
+    }
+
+    Ok(Some(std::fs::read_to_string(expanded)?))
+}
+
 /// Get all repositories to analyze
-fn get_repositories(args: &Args) -> Result<Vec<RepoInfo>> {
+fn get_repositories(args: &Args, config: Option<&Config>) -> Result<Vec<RepoInfo>> {
     // Priority: --repo flag > config file > current directory
 
     // 1. --repo flag takes highest priority (single repo)
@@ -170,18 +418,16 @@ fn get_repositories(args: &Args) -> Result<Vec<RepoInfo>> {
         return Ok(vec![RepoInfo {
             path: expanded,
             name,
-            branch: args.branch.clone(),
+            overrides: RepoOverrides::default(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            pathspec: Vec::new(),
         }]);
     }
 
-    // 2. Try to load config file
-    let config_path = args.config.clone().or_else(default_config_path);
-
-    if let Some(path) = config_path
-        && path.exists()
-    {
-        let config = load_config(&path)?;
-        let repos = filter_and_validate_repos(&config.repositories, args.repo_name.as_deref());
+    // 2. Use repositories from the config file
+    if let Some(config) = config {
+        let repos = filter_repos(&config.repositories, args.repo_name.as_deref());
 
         if !repos.is_empty() {
             return Ok(repos);
@@ -203,34 +449,174 @@ fn get_repositories(args: &Args) -> Result<Vec<RepoInfo>> {
     Ok(vec![RepoInfo {
         path: current_dir,
         name,
-        branch: args.branch.clone(),
+        overrides: RepoOverrides::default(),
+        include: Vec::new(),
+        exclude: Vec::new(),
+        pathspec: Vec::new(),
     }])
 }
 
-/// Filter repositories by name and validate they exist
-fn filter_and_validate_repos(repos: &[RepoConfig], filter: Option<&[String]>) -> Vec<RepoInfo> {
+/// Filter repositories by name
+///
+/// Repositories that no longer exist are kept, so they are reported as warnings
+/// when they fail to open.
+fn filter_repos(repos: &[RepoConfig], filter: Option<&[String]>) -> Vec<RepoInfo> {
     repos
         .iter()
-        .filter(|repo| {
-            // Filter by name if specified
-            if let Some(names) = filter
-                && !names.iter().any(|n| n == &repo.name)
-            {
-                return false;
-            }
-
-            // Validate repository exists
-            let expanded = expand_tilde(&repo.path);
-            expanded.exists() && (expanded.join(".git").exists() || expanded.join("HEAD").exists())
-        })
+        .filter(|repo| filter.is_none_or(|names| names.iter().any(|n| n == &repo.name)))
         .map(|repo| RepoInfo {
             path: expand_tilde(&repo.path),
             name: repo.name.clone(),
-            branch: repo.branch.clone(),
+            overrides: RepoOverrides::from_config(repo),
+            include: repo.include.clone(),
+            exclude: repo.exclude.clone(),
+            pathspec: Vec::new(),
+        })
+        .collect()
+}
+
+/// Add the checked-out submodules of repositories that recurse into them
+///
+/// Each submodule becomes its own repository named `parent/path`, with the
+/// parent's path filters re-rooted onto it (see [`submodule_info`]). Nested
+/// submodules are followed as well. When the submodules of a repository cannot
+/// be listed (e.g. a broken `.gitmodules`), they are skipped with a warning named
+/// `parent/*`, unless `--strict` is set.
+fn expand_submodules(
+    repos: Vec<RepoInfo>,
+    args: &Args,
+) -> Result<(Vec<RepoInfo>, Vec<RepoWarning>)> {
+    let mut expanded = Vec::with_capacity(repos.len());
+    let mut warnings = Vec::new();
+    let mut pending = repos;
+    pending.reverse();
+
+    while let Some(repo_info) = pending.pop() {
+        let recurse = RepoSettings::resolve(args, &repo_info.overrides)
+            .recurse_submodules
+            .value;
+        // A repository that fails to open is reported when it is analyzed
+        if recurse && let Ok(repo) = Repository::open(&repo_info.path, &repo_info.name) {
+            let submodules = match repo.submodules() {
+                Ok(submodules) => submodules,
+                Err(err) if args.strict => return Err(err),
+                Err(err) => {
+                    warnings.push(RepoWarning::new(format!("{}/*", repo_info.name), err));
+                    Vec::new()
+                }
+            };
+            for (sub_path, path) in submodules.into_iter().rev() {
+                pending.extend(submodule_info(&repo_info, &sub_path, path));
+            }
+        }
+        expanded.push(repo_info);
+    }
+
+    Ok((expanded, warnings))
+}
+
+/// Repository info of a submodule at `sub_path` inside `parent`
+///
+/// The parent's include/exclude globs and pathspec are rooted at the parent, so
+/// they are re-rooted onto the submodule. Returns `None` if the parent's include
+/// globs or pathspec leave nothing inside the submodule to count.
+fn submodule_info(parent: &RepoInfo, sub_path: &str, path: PathBuf) -> Option<RepoInfo> {
+    let include = reroot_globs(&parent.include, sub_path);
+    if include.is_empty() && !parent.include.is_empty() {
+        return None;
+    }
+
+    Some(RepoInfo {
+        path,
+        name: format!("{}/{sub_path}", parent.name),
+        // A submodule's branches differ from its parent's
+        overrides: RepoOverrides {
+            branch: None,
+            ..parent.overrides.clone()
+        },
+        include,
+        exclude: reroot_globs(&parent.exclude, sub_path),
+        pathspec: reroot_pathspec(&parent.pathspec, sub_path)?,
+    })
+}
+
+/// Re-root gitignore-style globs of a parent repository onto its submodule
+///
+/// Unanchored globs such as `*.lock` apply anywhere and are kept. Anchored globs
+/// matching the submodule directory (e.g. `libs/` or `libs/**`) cover all of it,
+/// and those under it are made relative to it. Other anchored globs are dropped.
+fn reroot_globs(patterns: &[String], sub_path: &str) -> Vec<String> {
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            let (negation, glob) = match pattern.strip_prefix('!') {
+                Some(glob) => ("!", glob),
+                None => ("", pattern.as_str()),
+            };
+            let trailing = if glob.ends_with('/') { "/" } else { "" };
+            let glob = glob.trim_end_matches('/');
+            if !glob.contains('/') {
+                return Some(pattern.clone());
+            }
+
+            let glob = glob.trim_start_matches('/');
+            let covers_submodule = PathFilter::new(&[format!("/{glob}")], &[])
+                .is_ok_and(|filter| filter.matches(sub_path));
+            if covers_submodule {
+                return Some(format!("{negation}*"));
+            }
+            match strip_parent_path(glob, sub_path)? {
+                "" | "**" => Some(format!("{negation}*")),
+                rest => Some(format!("{negation}/{rest}{trailing}")),
+            }
         })
         .collect()
 }
 
+/// Re-root a parent repository's git pathspec onto its submodule
+///
+/// Entries under the submodule are made relative to it, and an entry naming the
+/// submodule or a directory above it covers all of it (an empty pathspec).
+/// Globs without a `/`, such as `*.proto`, match at any depth and are kept.
+/// Returns `None` if the pathspec reaches nothing inside the submodule.
+fn reroot_pathspec(pathspec: &[String], sub_path: &str) -> Option<Vec<String>> {
+    if pathspec.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut rerooted = Vec::new();
+    for spec in pathspec {
+        let spec_path = spec.trim_end_matches('/');
+        if !spec_path.contains('/') && spec_path.contains(['*', '?', '[']) {
+            rerooted.push(spec.clone());
+            continue;
+        }
+        match strip_parent_path(spec_path, sub_path) {
+            Some("") => return Some(Vec::new()),
+            Some(rest) => rerooted.push(rest.to_string()),
+            None => {}
+        }
+    }
+
+    (!rerooted.is_empty()).then_some(rerooted)
+}
+
+/// The part of a parent-relative path inside the submodule at `sub_path`
+///
+/// Returns `""` if the path is the submodule itself or a directory above it
+/// (including the root, `""` or `.`), and `None` if it lies outside the submodule.
+fn strip_parent_path<'a>(path: &'a str, sub_path: &str) -> Option<&'a str> {
+    if path.is_empty()
+        || path == "."
+        || sub_path
+            .strip_prefix(path)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    {
+        return Some("");
+    }
+    path.strip_prefix(sub_path)?.strip_prefix('/')
+}
+
 /// Execute the `add` subcommand
 fn execute_add(add_args: AddArgs, config_path: Option<PathBuf>) -> Result<()> {
     // Resolve the path
@@ -275,6 +661,7 @@ fn execute_add(add_args: AddArgs, config_path: Option<PathBuf>) -> Result<()> {
             ),
             repositories: Vec::new(),
             defaults: Defaults::default(),
+            mailmap: None,
         }
     };
 
@@ -296,6 +683,12 @@ fn execute_add(add_args: AddArgs, config_path: Option<PathBuf>) -> Result<()> {
         name: name.clone(),
         path: path_for_storage.clone(),
         branch: add_args.branch,
+        include: Vec::new(),
+        exclude: Vec::new(),
+        ext: None,
+        exclude_merges: None,
+        first_parent: None,
+        recurse_submodules: None,
     };
     config.repositories.push(repo_config);
 
@@ -441,6 +834,86 @@ fn execute_list(list_args: ListArgs, config_path: Option<PathBuf>) -> Result<()>
     Ok(())
 }
 
+/// Execute the `cache` subcommand
+// Takes ownership for consistency with other execute_* functions
+#[allow(clippy::needless_pass_by_value)]
+fn execute_cache(cache_args: CacheArgs, cache_dir: Option<PathBuf>) -> Result<()> {
+    let Some(cache_dir) = cache_dir.or_else(default_cache_dir) else {
+        println!("No cache directory available.");
+        return Ok(());
+    };
+
+    match cache_args.action {
+        CacheAction::Stats => {
+            let stats = cache_stats(&cache_dir)?;
+            println!("Cache: {}", shorten_home_path(&cache_dir).display());
+            println!("  Repositories: {}", stats.repositories);
+            println!("  Commits: {}", stats.commits);
+            println!("  Size: {} bytes", stats.bytes);
+        }
+        CacheAction::Clear => {
+            clear_cache(&cache_dir)?;
+            println!("Cleared cache: {}", shorten_home_path(&cache_dir).display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute the `config` subcommand
+fn execute_config(config_args: &ConfigArgs, mut args: Args) -> Result<()> {
+    let ConfigAction::Show { resolved } = config_args.action;
+    let config_file = args.config.clone().or_else(default_config_path);
+    let config = match &config_file {
+        Some(path) if path.exists() => Some(load_config(path)?),
+        _ => None,
+    };
+
+    match (&config_file, &config) {
+        (Some(path), Some(_)) => println!("Config: {}", shorten_home_path(path).display()),
+        _ => println!("No config file found."),
+    }
+
+    if !resolved {
+        if let Some(config) = &config {
+            println!("{}", serde_json::to_string_pretty(config)?);
+        }
+        return Ok(());
+    }
+
+    if let Some(config) = &config {
+        apply_config_defaults(&mut args, &config.defaults);
+    }
+    println!();
+    print_settings(&resolved_options(&args));
+
+    for repo in config.iter().flat_map(|config| &config.repositories) {
+        let settings = RepoSettings::resolve(&args, &RepoOverrides::from_config(repo));
+        let overridden = settings.overridden();
+        if overridden.is_empty() {
+            continue;
+        }
+        println!();
+        println!("Repository: {}", repo.name);
+        let rows: Vec<_> = overridden
+            .into_iter()
+            .map(|(option, value)| (option, value, "repository".to_string()))
+            .collect();
+        print_settings(&rows);
+    }
+
+    Ok(())
+}
+
+/// Print `(option, value, source)` rows in aligned columns
+fn print_settings(rows: &[(&str, String, String)]) {
+    let option_width = rows.iter().map(|(o, _, _)| o.len()).max().unwrap_or(0);
+    let value_width = rows.iter().map(|(_, v, _)| v.len()).max().unwrap_or(0);
+    for (option, value, source) in rows {
+        println!("  {option:<option_width$}  {value:<value_width$}  ({source})");
+    }
+}
+
 /// Print repositories in table format
 fn print_repo_table(repos: &[(&crate::config::RepoConfig, bool)]) {
     // Calculate column widths
@@ -503,46 +976,18 @@ fn shorten_home_path(path: &Path) -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::process::Command;
+    use crate::cli::args::{MetricGroup, Period, WeekLabel, WeekStart};
+    use crate::git::{DiffStats, FileChange};
+    use crate::test_support::{self, create_test_repo, git};
+    use clap::Parser;
     use tempfile::TempDir;
 
-    fn create_test_repo() -> TempDir {
-        let dir = TempDir::new().unwrap();
-        let path = dir.path();
-
-        Command::new("git")
-            .args(["init"])
-            .current_dir(path)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["config", "user.email", "test@example.com"])
-            .current_dir(path)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["config", "user.name", "Test User"])
-            .current_dir(path)
-            .output()
-            .unwrap();
-
-        std::fs::write(path.join("README.md"), "# Test\n").unwrap();
-
-        Command::new("git")
-            .args(["add", "."])
-            .current_dir(path)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["commit", "-m", "Initial commit"])
-            .current_dir(path)
-            .output()
-            .unwrap();
-
-        dir
+    /// Analysis arguments with every option at its CLI default, caching in a temp dir
+    fn default_args() -> Args {
+        Args {
+            cache_dir: Some(test_support::cache_dir()),
+            ..Args::parse_from(["kodo"])
+        }
     }
 
     #[test]
@@ -550,17 +995,10 @@ mod tests {
         let dir = create_test_repo();
 
         let args = Args {
-            command: None,
             config: None,
             repo: Some(dir.path().to_path_buf()),
-            days: 7,
-            include_merges: false,
             output: OutputFormat::Json,
-            period: crate::cli::args::Period::Daily,
-            branch: None,
-            ext: None,
-            single_metric: false,
-            repo_name: None,
+            ..default_args()
         };
 
         let result = execute(args);
@@ -572,17 +1010,10 @@ mod tests {
         let dir = create_test_repo();
 
         let args = Args {
-            command: None,
             config: None,
             repo: Some(dir.path().to_path_buf()),
-            days: 7,
-            include_merges: false,
             output: OutputFormat::Table,
-            period: crate::cli::args::Period::Daily,
-            branch: None,
-            ext: None,
-            single_metric: false,
-            repo_name: None,
+            ..default_args()
         };
 
         let result = execute(args);
@@ -594,40 +1025,58 @@ mod tests {
         let dir = create_test_repo();
 
         let args = Args {
-            command: None,
             config: None,
             repo: Some(dir.path().to_path_buf()),
-            days: 7,
-            include_merges: false,
             output: OutputFormat::Csv,
-            period: crate::cli::args::Period::Daily,
-            branch: None,
-            ext: None,
-            single_metric: false,
-            repo_name: None,
+            ..default_args()
         };
 
         let result = execute(args);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_execute_with_author_filter_and_breakdown() {
+        let dir = create_test_repo();
+
+        let args = Args {
+            config: None,
+            repo: Some(dir.path().to_path_buf()),
+            output: OutputFormat::Csv,
+            author: vec!["test@example".to_string()],
+            by_author: true,
+            ..default_args()
+        };
+
+        let result = execute(args);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_execute_with_invalid_author_pattern() {
+        let dir = create_test_repo();
+
+        let args = Args {
+            config: None,
+            repo: Some(dir.path().to_path_buf()),
+            author: vec!["(".to_string()],
+            ..default_args()
+        };
+
+        let result = execute(args);
+        assert!(matches!(result, Err(Error::InvalidPattern { .. })));
+    }
+
     #[test]
     fn test_get_repositories_with_repo_arg() {
         let args = Args {
-            command: None,
             config: None,
             repo: Some(PathBuf::from("/tmp/test-repo")),
-            days: 7,
-            include_merges: false,
             output: OutputFormat::Json,
-            period: crate::cli::args::Period::Daily,
-            branch: None,
-            ext: None,
-            single_metric: false,
-            repo_name: None,
+            ..default_args()
         };
 
-        let result = get_repositories(&args);
+        let result = get_repositories(&args, None);
         assert!(result.is_ok());
 
         let repos = result.unwrap();
@@ -637,13 +1086,219 @@ mod tests {
     }
 
     #[test]
-    fn test_filter_and_validate_repos() {
+    fn test_resolve_date_range_defaults_to_last_n_days() {
+        let today = NaiveDate::from_ymd_opt(2026, 8, 12).unwrap();
+        let range = resolve_date_range(&default_args(), today).unwrap();
+
+        assert_eq!(range.to, today);
+        assert_eq!(range.from, NaiveDate::from_ymd_opt(2026, 8, 5).unwrap());
+    }
+
+    #[test]
+    fn test_resolve_date_range_explicit() {
+        let today = NaiveDate::from_ymd_opt(2026, 8, 12).unwrap();
+        let args = Args {
+            from: Some("start of last quarter".to_string()),
+            to: Some("end of last quarter".to_string()),
+            ..default_args()
+        };
+        let range = resolve_date_range(&args, today).unwrap();
+
+        assert_eq!(range.from, NaiveDate::from_ymd_opt(2026, 4, 1).unwrap());
+        assert_eq!(range.to, NaiveDate::from_ymd_opt(2026, 6, 30).unwrap());
+    }
+
+    #[test]
+    fn test_resolve_date_range_days_before_to() {
+        let today = NaiveDate::from_ymd_opt(2026, 8, 12).unwrap();
+        let args = Args {
+            to: Some("2026-01-31".to_string()),
+            days: 30,
+            ..default_args()
+        };
+        let range = resolve_date_range(&args, today).unwrap();
+
+        assert_eq!(range.from, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert_eq!(range.to, NaiveDate::from_ymd_opt(2026, 1, 31).unwrap());
+    }
+
+    #[test]
+    fn test_resolve_date_range_errors() {
+        let today = NaiveDate::from_ymd_opt(2026, 8, 12).unwrap();
+
+        let args = Args {
+            from: Some("next tuesday".to_string()),
+            ..default_args()
+        };
+        assert!(matches!(
+            resolve_date_range(&args, today),
+            Err(Error::InvalidDate { .. })
+        ));
+
+        let args = Args {
+            from: Some("2026-02-01".to_string()),
+            to: Some("2026-01-01".to_string()),
+            ..default_args()
+        };
+        assert!(matches!(
+            resolve_date_range(&args, today),
+            Err(Error::InvalidDateRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolve_grouping() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let args = Args {
+            period: Period::Weekly,
+            week_start: WeekStart::Sunday,
+            ..default_args()
+        };
+        assert_eq!(
+            resolve_grouping(&args, today).unwrap(),
+            Grouping::Calendar {
+                period: Period::Weekly,
+                week_start: WeekStart::Sunday,
+                week_label: WeekLabel::Iso,
+            }
+        );
+
+        let args = Args {
+            bucket: Some(14),
+            bucket_start: Some("2026-10-05".to_string()),
+            ..args
+        };
+        assert_eq!(
+            resolve_grouping(&args, today).unwrap(),
+            Grouping::Buckets {
+                days: 14,
+                start: NaiveDate::from_ymd_opt(2026, 10, 5),
+            }
+        );
+
+        let mut args = Args::parse_layered_from(["kodo", "--period", "monthly"]);
+        apply_config_defaults(
+            &mut args,
+            &Defaults {
+                bucket: Some(14),
+                ..Defaults::default()
+            },
+        );
+        assert!(matches!(
+            resolve_grouping(&args, today).unwrap(),
+            Grouping::Calendar {
+                period: Period::Monthly,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_execute_with_rev() {
+        let dir = create_test_repo();
+
+        let args = Args {
+            config: None,
+            repo: Some(dir.path().to_path_buf()),
+            output: OutputFormat::Json,
+            rev: Some("HEAD".to_string()),
+            ..default_args()
+        };
+        assert!(execute(args).is_ok());
+
+        let args = Args {
+            config: None,
+            repo: Some(dir.path().to_path_buf()),
+            rev: Some("missing..HEAD".to_string()),
+            ..default_args()
+        };
+        assert!(matches!(execute(args), Err(Error::Git(_))));
+    }
+
+    #[test]
+    fn test_read_global_mailmap() {
+        let dir = TempDir::new().unwrap();
+        let mailmap_path = dir.path().join("mailmap");
+        std::fs::write(&mailmap_path, "A <a@example.com> <b@example.com>\n").unwrap();
+
+        let mut config = Config {
+            schema: None,
+            repositories: Vec::new(),
+            defaults: Defaults::default(),
+            mailmap: None,
+        };
+        assert!(read_global_mailmap(&config).unwrap().is_none());
+
+        config.mailmap = Some(mailmap_path);
+        let contents = read_global_mailmap(&config).unwrap().unwrap();
+        assert!(contents.contains("<b@example.com>"));
+
+        config.mailmap = Some(dir.path().join("missing"));
+        assert!(matches!(
+            read_global_mailmap(&config),
+            Err(Error::ConfigInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_filter_repos() {
         // Empty list should return empty
         let repos: Vec<RepoConfig> = vec![];
-        let result = filter_and_validate_repos(&repos, None);
+        let result = filter_repos(&repos, None);
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn test_filter_repos_keeps_missing_paths() {
+        let repo = |name: &str| RepoConfig {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/nonexistent/{name}")),
+            branch: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            ext: None,
+            exclude_merges: None,
+            first_parent: None,
+            recurse_submodules: None,
+        };
+        let repos = vec![repo("api"), repo("web")];
+
+        assert_eq!(filter_repos(&repos, None).len(), 2);
+        let names = ["web".to_string()];
+        let result = filter_repos(&repos, Some(&names));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "web");
+    }
+
+    #[test]
+    fn test_split_failures() {
+        let walk = CommitWalk::default;
+        let missing = || Error::RepoNotFound {
+            path: PathBuf::from("/gone"),
+        };
+        let results = || {
+            vec![
+                ("api".to_string(), Ok(walk())),
+                ("gone".to_string(), Err(missing())),
+            ]
+        };
+
+        let (walks, warnings) = split_failures(results(), false).unwrap();
+        assert_eq!(walks.len(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].repository, "gone");
+        assert!(matches!(*warnings[0].error, Error::RepoNotFound { .. }));
+
+        assert!(matches!(
+            split_failures(results(), true),
+            Err(Error::RepoNotFound { .. })
+        ));
+        assert!(matches!(
+            split_failures(vec![("gone".to_string(), Err(missing()))], false),
+            Err(Error::RepoNotFound { .. })
+        ));
+    }
+
     #[test]
     fn test_execute_list_no_config() {
         // Test list with non-existent config file
@@ -674,8 +1329,15 @@ mod tests {
                 name: "test-repo".to_string(),
                 path: dir.path().to_path_buf(),
                 branch: Some("main".to_string()),
+                include: Vec::new(),
+                exclude: Vec::new(),
+                ext: None,
+                exclude_merges: None,
+                first_parent: None,
+                recurse_submodules: None,
             }],
             defaults: Defaults::default(),
+            mailmap: None,
         };
         save_config(&config, &config_path).unwrap();
 
@@ -715,33 +1377,255 @@ mod tests {
                     name: "repo1".to_string(),
                     path: repo1.path().to_path_buf(),
                     branch: None,
+                    include: Vec::new(),
+                    exclude: Vec::new(),
+                    ext: None,
+                    exclude_merges: None,
+                    first_parent: None,
+                    recurse_submodules: None,
                 },
                 RepoConfig {
                     name: "repo2".to_string(),
                     path: repo2.path().to_path_buf(),
                     branch: None,
+                    include: Vec::new(),
+                    exclude: Vec::new(),
+                    ext: None,
+                    exclude_merges: None,
+                    first_parent: None,
+                    recurse_submodules: None,
                 },
             ],
             defaults: Defaults::default(),
+            mailmap: None,
         };
         save_config(&config, &config_path).unwrap();
 
         // Execute with multiple repos (tests parallel processing)
         let args = Args {
-            command: None,
             config: Some(config_path),
             repo: None,
-            days: 7,
-            include_merges: false,
             output: OutputFormat::Json,
-            period: crate::cli::args::Period::Daily,
-            branch: None,
-            ext: None,
-            single_metric: false,
-            repo_name: None,
+            ..default_args()
         };
 
         let result = execute(args);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_execute_with_path_globs() {
+        let dir = create_test_repo();
+
+        let args = Args {
+            config: None,
+            repo: Some(dir.path().to_path_buf()),
+            output: OutputFormat::Json,
+            include: vec!["src/".to_string()],
+            exclude: vec!["*.lock".to_string()],
+            ..default_args()
+        };
+        assert!(execute(args).is_ok());
+
+        let args = Args {
+            config: None,
+            repo: Some(dir.path().to_path_buf()),
+            output: OutputFormat::Json,
+            exclude: vec!["{src,docs".to_string()],
+            ..default_args()
+        };
+        assert!(matches!(execute(args), Err(Error::InvalidPattern { .. })));
+    }
+
+    #[test]
+    fn test_apply_path_filter_with_extensions() {
+        let mut diff = DiffStats::default();
+        diff.add_file(FileChange::new("src/main.rs".to_string(), 100, 10));
+        diff.add_file(FileChange::new("docs/guide.md".to_string(), 50, 5));
+        diff.add_file(FileChange::binary("assets/logo.png".to_string(), 100, 300));
+        let mut commits = vec![CommitInfo {
+            diff,
+            ..Default::default()
+        }];
+        let repo_info = RepoInfo {
+            path: PathBuf::from("."),
+            name: "test".to_string(),
+            overrides: RepoOverrides::default(),
+            include: Vec::new(),
+            exclude: vec!["docs/".to_string()],
+            pathspec: Vec::new(),
+        };
+
+        let extensions = vec!["rs".to_string(), "md".to_string()];
+        apply_path_filter(&mut commits, &repo_info, Some(&extensions)).unwrap();
+
+        // Only the .rs file matches both the extensions and the repository's excludes
+        let diff = &commits[0].diff;
+        assert_eq!(diff.additions, 100);
+        assert_eq!(diff.deletions, 10);
+        assert_eq!(diff.files_changed, 1);
+        assert_eq!(diff.binary_files, 0);
+    }
+
+    #[test]
+    fn test_execute_with_cache_dir() {
+        let dir = create_test_repo();
+        let cache_dir = TempDir::new().unwrap();
+
+        let args = Args {
+            config: None,
+            repo: Some(dir.path().to_path_buf()),
+            output: OutputFormat::Json,
+            cache_dir: Some(cache_dir.path().to_path_buf()),
+            no_cache: false,
+            ..default_args()
+        };
+        assert!(execute(args).is_ok());
+        assert_eq!(cache_stats(cache_dir.path()).unwrap().repositories, 1);
+
+        let clear = CacheArgs {
+            action: CacheAction::Clear,
+        };
+        assert!(execute_cache(clear, Some(cache_dir.path().to_path_buf())).is_ok());
+        assert_eq!(cache_stats(cache_dir.path()).unwrap().repositories, 0);
+    }
+
+    #[test]
+    fn test_execute_commits_only_metrics() {
+        let dir = create_test_repo();
+
+        let args = Args {
+            config: None,
+            repo: Some(dir.path().to_path_buf()),
+            output: OutputFormat::Csv,
+            metrics: vec![MetricGroup::Commits],
+            by_author: true,
+            ..default_args()
+        };
+        assert!(execute(args).is_ok());
+    }
+
+    #[test]
+    fn test_expand_submodules() {
+        let parent = create_test_repo();
+        let sub = create_test_repo();
+        git(
+            parent.path(),
+            &[
+                "-c",
+                "protocol.file.allow=always",
+                "submodule",
+                "add",
+                "-q",
+                sub.path().to_str().unwrap(),
+                "vendor/lib",
+            ],
+        );
+
+        let repo_info = |recurse_submodules| RepoInfo {
+            path: parent.path().to_path_buf(),
+            name: "app".to_string(),
+            overrides: RepoOverrides {
+                branch: Some("main".to_string()),
+                first_parent: Some(true),
+                recurse_submodules,
+                ..RepoOverrides::default()
+            },
+            include: vec!["src/".to_string()],
+            exclude: Vec::new(),
+            pathspec: Vec::new(),
+        };
+        let recurse_all = Args {
+            recurse_submodules: true,
+            ..default_args()
+        };
+        let strict = Args {
+            strict: true,
+            ..default_args()
+        };
+
+        let (repos, warnings) = expand_submodules(vec![repo_info(None)], &default_args()).unwrap();
+        assert_eq!(repos.len(), 1);
+        assert!(warnings.is_empty());
+        let (repos, _) = expand_submodules(vec![repo_info(Some(false))], &recurse_all).unwrap();
+        assert_eq!(repos.len(), 1);
+
+        for (repos, _) in [
+            expand_submodules(vec![repo_info(Some(true))], &default_args()).unwrap(),
+            expand_submodules(vec![repo_info(None)], &recurse_all).unwrap(),
+        ] {
+            let names: Vec<_> = repos.iter().map(|r| r.name.as_str()).collect();
+            assert_eq!(names, ["app", "app/vendor/lib"]);
+            assert_eq!(repos[1].path, parent.path().join("vendor/lib"));
+            assert_eq!(repos[1].overrides.branch, None);
+            assert_eq!(repos[1].overrides.first_parent, Some(true));
+            assert_eq!(repos[1].include, vec!["src/".to_string()]);
+        }
+
+        // A broken .gitmodules skips the submodules, or fails with --strict
+        std::fs::write(parent.path().join(".gitmodules"), "[submodule\n").unwrap();
+        let (repos, warnings) =
+            expand_submodules(vec![repo_info(Some(true))], &default_args()).unwrap();
+        let names: Vec<_> = repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["app"]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].repository, "app/*");
+        assert!(expand_submodules(vec![repo_info(Some(true))], &strict).is_err());
+    }
+
+    #[test]
+    fn test_reroot_globs() {
+        let patterns: Vec<String> = [
+            "*.lock",
+            "vendor/",
+            "/libs/foo/src/**",
+            "libs/foo/docs/",
+            "!libs/foo/README.md",
+            "libs/foo/**",
+            "/libs",
+            "libs/*",
+            "/docs",
+            "libs/bar/src",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        assert_eq!(
+            reroot_globs(&patterns, "libs/foo"),
+            [
+                "*.lock",
+                "vendor/",
+                "/src/**",
+                "/docs/",
+                "!/README.md",
+                "*",
+                "*",
+                "*"
+            ]
+        );
+        assert!(reroot_globs(&patterns[8..], "libs/foo").is_empty());
+    }
+
+    #[test]
+    fn test_reroot_pathspec() {
+        let spec = |specs: &[&str]| specs.iter().map(|s| (*s).to_string()).collect::<Vec<_>>();
+
+        assert_eq!(reroot_pathspec(&[], "libs/foo"), Some(Vec::new()));
+        assert_eq!(
+            reroot_pathspec(&spec(&["libs/foo/src", "*.proto", "docs"]), "libs/foo"),
+            Some(spec(&["src", "*.proto"]))
+        );
+        for covering in ["libs/foo", "libs/foo/", "libs", "."] {
+            assert_eq!(
+                reroot_pathspec(&spec(&[covering, "libs/foo/src"]), "libs/foo"),
+                Some(Vec::new()),
+                "{covering}"
+            );
+        }
+        assert_eq!(
+            reroot_pathspec(&spec(&["libs/foobar", "README.md"]), "libs/foo"),
+            None
+        );
+    }
 }
@@ -0,0 +1,444 @@
+//! Layering of config defaults and per-repository overrides onto the CLI arguments
+//!
+//! Precedence, lowest first: built-in defaults, the config `defaults` block,
+//! the repository's config entry, `KODO_*` environment variables, CLI flags.
+//! Clap already resolves the first and the last two layers; this module fills
+//! in the config layers for options that were not set through env or CLI.
+
+use crate::cli::args::{Args, LayeredOption, MetricGroup, Source};
+use crate::config::{Defaults, RepoConfig};
+use clap::CommandFactory;
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+/// An effective option value and where it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting<T> {
+    /// Effective value
+    pub value: T,
+    /// Layer the value came from
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    /// Replace the value with one from a config layer, unless env or CLI set it
+    #[must_use]
+    pub fn layer(self, value: Option<T>, source: Source) -> Self {
+        match value {
+            Some(value) if self.source < Source::Env => Self { value, source },
+            _ => self,
+        }
+    }
+}
+
+/// Options a repository's config entry can override
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepoOverrides {
+    /// Branch to analyze
+    pub branch: Option<String>,
+    /// File extensions to include
+    pub ext: Option<Vec<String>>,
+    /// Exclude merge commits
+    pub exclude_merges: Option<bool>,
+    /// Follow only first parents
+    pub first_parent: Option<bool>,
+    /// Also analyze checked-out submodules
+    pub recurse_submodules: Option<bool>,
+}
+
+impl RepoOverrides {
+    /// Overrides set in a repository's config entry
+    #[must_use]
+    pub fn from_config(repo: &RepoConfig) -> Self {
+        Self {
+            branch: repo.branch.clone(),
+            ext: repo.ext.clone(),
+            exclude_merges: repo.exclude_merges,
+            first_parent: repo.first_parent,
+            recurse_submodules: repo.recurse_submodules,
+        }
+    }
+}
+
+/// Walk options of one repository after applying its overrides
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoSettings {
+    /// Branches to analyze (empty for the default branch)
+    pub branch: Setting<Vec<String>>,
+    /// File extensions to include
+    pub ext: Setting<Option<Vec<String>>>,
+    /// Include merge commits
+    pub include_merges: Setting<bool>,
+    /// Follow only first parents
+    pub first_parent: Setting<bool>,
+    /// Also analyze checked-out submodules
+    pub recurse_submodules: Setting<bool>,
+}
+
+impl RepoSettings {
+    /// Resolve the walk options of a repository from the (config-resolved) arguments
+    #[must_use]
+    pub fn resolve(args: &Args, overrides: &RepoOverrides) -> Self {
+        Self {
+            branch: Setting {
+                value: args.branch.clone(),
+                source: args.source(LayeredOption::Branch),
+            }
+            .layer(
+                overrides.branch.clone().map(|b| vec![b]),
+                Source::Repository,
+            ),
+            ext: Setting {
+                value: args.ext.clone(),
+                source: args.source(LayeredOption::Ext),
+            }
+            .layer(overrides.ext.clone().map(Some), Source::Repository),
+            include_merges: Setting {
+                value: args.include_merges,
+                source: args.source(LayeredOption::IncludeMerges),
+            }
+            .layer(overrides.exclude_merges.map(|e| !e), Source::Repository),
+            first_parent: Setting {
+                value: args.first_parent,
+                source: args.source(LayeredOption::FirstParent),
+            }
+            .layer(overrides.first_parent, Source::Repository),
+            recurse_submodules: Setting {
+                value: args.recurse_submodules,
+                source: args.source(LayeredOption::RecurseSubmodules),
+            }
+            .layer(overrides.recurse_submodules, Source::Repository),
+        }
+    }
+
+    /// Whether merge commits are skipped (first-parent mode keeps them)
+    #[must_use]
+    pub fn exclude_merges(&self) -> bool {
+        !self.include_merges.value && !self.first_parent.value
+    }
+
+    /// Settings overridden by the repository, as `(option, value)` pairs
+    #[must_use]
+    pub fn overridden(&self) -> Vec<(&'static str, String)> {
+        let mut overridden = Vec::new();
+        if self.branch.source == Source::Repository {
+            overridden.push(("branch", self.branch.value.join(",")));
+        }
+        if self.ext.source == Source::Repository {
+            overridden.push(("ext", describe_list(self.ext.value.as_deref())));
+        }
+        if self.include_merges.source == Source::Repository {
+            overridden.push(("include_merges", self.include_merges.value.to_string()));
+        }
+        if self.first_parent.source == Source::Repository {
+            overridden.push(("first_parent", self.first_parent.value.to_string()));
+        }
+        if self.recurse_submodules.source == Source::Repository {
+            overridden.push((
+                "recurse_submodules",
+                self.recurse_submodules.value.to_string(),
+            ));
+        }
+        overridden
+    }
+}
+
+/// Fill in options from the config `defaults` block
+pub fn apply_config_defaults(args: &mut Args, defaults: &Defaults) {
+    for &option in LayeredOption::ALL {
+        // Each option's `Args` field and config key share its id
+        macro_rules! layer {
+            ($field:ident, $value:expr) => {
+                layer(&mut args.sources, option, &mut args.$field, $value)
+            };
+        }
+
+        match option {
+            LayeredOption::Days => layer!(days, defaults.days),
+            LayeredOption::Period => layer!(period, defaults.period),
+            LayeredOption::WeekStart => layer!(week_start, defaults.week_start),
+            LayeredOption::WeekLabel => layer!(week_label, defaults.week_label),
+            LayeredOption::Bucket => layer!(bucket, defaults.bucket.map(Some)),
+            LayeredOption::BucketStart => {
+                layer!(bucket_start, defaults.bucket_start.clone().map(Some));
+            }
+            LayeredOption::Output => layer!(output, defaults.output),
+            LayeredOption::Tz => layer!(tz, defaults.tz),
+            LayeredOption::DateField => layer!(date_field, defaults.date_field),
+            LayeredOption::Metrics => layer!(metrics, defaults.metrics.clone()),
+            LayeredOption::Branch => layer!(branch, defaults.branch.clone()),
+            LayeredOption::AllBranches => layer!(all_branches, defaults.all_branches),
+            LayeredOption::Refs => layer!(refs, defaults.refs.clone()),
+            LayeredOption::Ext => layer!(ext, defaults.ext.clone().map(Some)),
+            LayeredOption::Include => layer!(include, defaults.include.clone()),
+            LayeredOption::Exclude => layer!(exclude, defaults.exclude.clone()),
+            LayeredOption::Author => layer!(author, defaults.author.clone()),
+            LayeredOption::ByAuthor => layer!(by_author, defaults.by_author),
+            LayeredOption::IncludeMerges => {
+                layer!(include_merges, defaults.exclude_merges.map(|e| !e));
+            }
+            LayeredOption::FirstParent => layer!(first_parent, defaults.first_parent),
+            LayeredOption::IncludeGenerated => {
+                layer!(include_generated, defaults.include_generated);
+            }
+            LayeredOption::RenameThreshold => {
+                layer!(rename_threshold, defaults.rename_threshold);
+            }
+            LayeredOption::NoRenames => layer!(no_renames, defaults.no_renames),
+            LayeredOption::SkewSlop => layer!(skew_slop, defaults.skew_slop),
+            LayeredOption::Jobs => layer!(jobs, defaults.jobs),
+            LayeredOption::RecurseSubmodules => {
+                layer!(recurse_submodules, defaults.recurse_submodules);
+            }
+            LayeredOption::Strict => layer!(strict, defaults.strict),
+        }
+    }
+}
+
+/// Replace an option with its config value unless env or CLI set it
+fn layer<T>(
+    sources: &mut HashMap<LayeredOption, Source>,
+    option: LayeredOption,
+    target: &mut T,
+    value: Option<T>,
+) {
+    let current = sources.get(&option).copied().unwrap_or(Source::Default);
+    if let Some(value) = value
+        && current < Source::Env
+    {
+        *target = value;
+        sources.insert(option, Source::Config);
+    }
+}
+
+/// Effective value of every layered option, as `(option, value, source)` rows
+///
+/// Environment sources name their variable, e.g. `env KODO_DAYS`.
+#[must_use]
+pub fn resolved_options(args: &Args) -> Vec<(&'static str, String, String)> {
+    let command = Args::command();
+    LayeredOption::ALL
+        .iter()
+        .map(|&option| {
+            let source = match (args.source(option), option.env(&command)) {
+                (Source::Env, Some(env)) => format!("env {env}"),
+                (source, _) => source.to_string(),
+            };
+            (option.id(), describe(args, option), source)
+        })
+        .collect()
+}
+
+/// Display form of an option's current value
+fn describe(args: &Args, option: LayeredOption) -> String {
+    match option {
+        LayeredOption::Days => args.days.to_string(),
+        LayeredOption::Period => args.period.to_string(),
+        LayeredOption::WeekStart => args.week_start.to_string(),
+        LayeredOption::WeekLabel => args.week_label.to_string(),
+        LayeredOption::Bucket => args
+            .bucket
+            .map_or_else(|| "-".to_string(), |days| format!("{days}d")),
+        LayeredOption::BucketStart => args.bucket_start.clone().unwrap_or_else(|| "-".to_string()),
+        LayeredOption::Output => args.output.to_string(),
+        LayeredOption::Tz => args.tz.to_string(),
+        LayeredOption::DateField => args.date_field.to_string(),
+        LayeredOption::Metrics => args
+            .metrics
+            .iter()
+            .filter_map(MetricGroup::to_possible_value)
+            .map(|value| value.get_name().to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+        LayeredOption::Branch if args.branch.is_empty() => "(default branch)".to_string(),
+        LayeredOption::Branch => args.branch.join(","),
+        LayeredOption::AllBranches => args.all_branches.to_string(),
+        LayeredOption::Refs => describe_list(Some(&args.refs)),
+        LayeredOption::Ext => describe_list(args.ext.as_deref()),
+        LayeredOption::Include => describe_list(Some(&args.include)),
+        LayeredOption::Exclude => describe_list(Some(&args.exclude)),
+        LayeredOption::Author => describe_list(Some(&args.author)),
+        LayeredOption::ByAuthor => args.by_author.to_string(),
+        LayeredOption::IncludeMerges => args.include_merges.to_string(),
+        LayeredOption::FirstParent => args.first_parent.to_string(),
+        LayeredOption::IncludeGenerated => args.include_generated.to_string(),
+        LayeredOption::RenameThreshold => args.rename_threshold.to_string(),
+        LayeredOption::NoRenames => args.no_renames.to_string(),
+        LayeredOption::SkewSlop => args.skew_slop.to_string(),
+        LayeredOption::Jobs => args.jobs.to_string(),
+        LayeredOption::RecurseSubmodules => args.recurse_submodules.to_string(),
+        LayeredOption::Strict => args.strict.to_string(),
+    }
+}
+
+fn describe_list(values: Option<&[String]>) -> String {
+    values.map_or_else(|| "(all)".to_string(), |values| values.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args::{OutputFormat, Period};
+
+    fn defaults() -> Defaults {
+        Defaults {
+            days: Some(30),
+            period: Some(Period::Weekly),
+            output: Some(OutputFormat::Json),
+            exclude_merges: Some(false),
+            ..Defaults::default()
+        }
+    }
+
+    #[test]
+    fn test_config_defaults_fill_unset_options() {
+        let mut args = Args::parse_layered_from(["kodo"]);
+        apply_config_defaults(&mut args, &defaults());
+
+        assert_eq!(args.days, 30);
+        assert_eq!(args.period, Period::Weekly);
+        assert_eq!(args.output, OutputFormat::Json);
+        assert!(args.include_merges);
+        assert_eq!(args.source(LayeredOption::Days), Source::Config);
+        assert_eq!(args.source(LayeredOption::Tz), Source::Default);
+    }
+
+    #[test]
+    fn test_cli_flags_win_over_config_defaults() {
+        let mut args = Args::parse_layered_from(["kodo", "--days", "3", "-p", "monthly"]);
+        apply_config_defaults(&mut args, &defaults());
+
+        assert_eq!(args.days, 3);
+        assert_eq!(args.period, Period::Monthly);
+        assert_eq!(args.output, OutputFormat::Json);
+        assert_eq!(args.source(LayeredOption::Days), Source::Cli);
+    }
+
+    #[test]
+    fn test_unrecorded_sources_count_as_defaults() {
+        use clap::Parser;
+
+        let mut args = Args::parse_from(["kodo", "--days", "3"]);
+        apply_config_defaults(&mut args, &defaults());
+        assert_eq!(args.days, 30);
+        assert_eq!(args.period, Period::Weekly);
+        assert_eq!(args.source(LayeredOption::Days), Source::Config);
+    }
+
+    #[test]
+    fn test_cli_can_turn_off_config_flags() {
+        let config = Defaults {
+            exclude_merges: Some(false),
+            first_parent: Some(true),
+            include_generated: Some(true),
+            strict: Some(true),
+            ..Defaults::default()
+        };
+
+        let mut args = Args::parse_layered_from(["kodo"]);
+        apply_config_defaults(&mut args, &config);
+        assert!(args.include_merges && args.first_parent && args.include_generated && args.strict);
+
+        let mut args = Args::parse_layered_from([
+            "kodo",
+            "--include-merges=false",
+            "--first-parent=false",
+            "--include-generated=false",
+            "--strict=false",
+        ]);
+        apply_config_defaults(&mut args, &config);
+        assert!(!args.include_merges);
+        assert!(!args.first_parent);
+        assert!(!args.include_generated);
+        assert!(!args.strict);
+        assert_eq!(args.source(LayeredOption::Strict), Source::Cli);
+    }
+
+    #[test]
+    fn test_config_layers_filters_and_walk_options() {
+        let config = Defaults {
+            include: Some(vec!["src/**".to_string()]),
+            author: Some(vec!["alice".to_string()]),
+            jobs: Some(2),
+            bucket: Some(14),
+            recurse_submodules: Some(true),
+            ..Defaults::default()
+        };
+
+        let mut args = Args::parse_layered_from(["kodo", "--author", "bob"]);
+        apply_config_defaults(&mut args, &config);
+        assert_eq!(args.include, vec!["src/**"]);
+        assert_eq!(args.author, vec!["bob"]);
+        assert_eq!(args.jobs, 2);
+        assert_eq!(args.bucket, Some(14));
+        assert!(args.recurse_submodules);
+        assert_eq!(args.source(LayeredOption::Author), Source::Cli);
+        assert_eq!(args.source(LayeredOption::Jobs), Source::Config);
+    }
+
+    #[test]
+    fn test_repo_overrides_sit_between_config_and_cli() {
+        let overrides = RepoOverrides {
+            branch: Some("develop".to_string()),
+            ext: Some(vec!["rs".to_string()]),
+            exclude_merges: Some(false),
+            first_parent: None,
+            recurse_submodules: Some(true),
+        };
+
+        let mut args = Args::parse_layered_from(["kodo"]);
+        apply_config_defaults(
+            &mut args,
+            &Defaults {
+                branch: Some(vec!["main".to_string()]),
+                ..Defaults::default()
+            },
+        );
+        let repo = RepoSettings::resolve(&args, &overrides);
+        assert_eq!(repo.branch.value, vec!["develop"]);
+        assert_eq!(repo.branch.source, Source::Repository);
+        assert_eq!(repo.ext.value, Some(vec!["rs".to_string()]));
+        assert!(!repo.exclude_merges());
+        assert_eq!(repo.first_parent.source, Source::Default);
+        assert_eq!(
+            repo.overridden(),
+            vec![
+                ("branch", "develop".to_string()),
+                ("ext", "rs".to_string()),
+                ("include_merges", "true".to_string()),
+                ("recurse_submodules", "true".to_string()),
+            ]
+        );
+
+        let args = Args::parse_layered_from(["kodo", "--branch", "release"]);
+        let repo = RepoSettings::resolve(&args, &overrides);
+        assert_eq!(repo.branch.value, vec!["release"]);
+        assert_eq!(repo.branch.source, Source::Cli);
+
+        let args = Args::parse_layered_from(["kodo", "--recurse-submodules=false"]);
+        let repo = RepoSettings::resolve(&args, &overrides);
+        assert!(!repo.recurse_submodules.value);
+        assert_eq!(repo.recurse_submodules.source, Source::Cli);
+    }
+
+    #[test]
+    fn test_resolved_options_lists_every_layered_option() {
+        let mut args = Args::parse_layered_from(["kodo", "--tz", "utc"]);
+        apply_config_defaults(&mut args, &defaults());
+        let rows = resolved_options(&args);
+
+        assert_eq!(rows.len(), LayeredOption::ALL.len());
+        assert!(rows.contains(&("days", "30".to_string(), "config".to_string())));
+        assert!(rows.contains(&("tz", "utc".to_string(), "cli".to_string())));
+        assert!(rows.contains(&(
+            "metrics",
+            "commits,lines,files,binary".to_string(),
+            "default".to_string()
+        )));
+        assert!(rows.contains(&(
+            "branch",
+            "(default branch)".to_string(),
+            "default".to_string()
+        )));
+    }
+}
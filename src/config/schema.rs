@@ -1,5 +1,8 @@
 //! Configuration schema definitions
 
+use crate::git::{DateField, Timezone};
+use crate::output::OutputFormat;
+use crate::stats::{MetricGroup, Period, WeekLabel, WeekStart};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -16,6 +19,10 @@ pub struct Config {
     /// Default settings
     #[serde(default)]
     pub defaults: Defaults,
+
+    /// Global mailmap file applied to every repository (supports ~)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mailmap: Option<PathBuf>,
 }
 
 /// Single repository configuration
@@ -29,35 +36,172 @@ pub struct RepoConfig {
 
     /// Default branch to analyze
     pub branch: Option<String>,
+
+    /// Gitignore-style globs; only matching files are counted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// Gitignore-style globs for files to skip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+
+    /// File extensions to include, overriding `defaults.ext`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext: Option<Vec<String>>,
+
+    /// Exclude merge commits, overriding `defaults.exclude_merges`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_merges: Option<bool>,
+
+    /// Follow only first parents, overriding `defaults.first_parent`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_parent: Option<bool>,
+
+    /// Also analyze checked-out submodules, overriding `defaults.recurse_submodules`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurse_submodules: Option<bool>,
 }
 
 /// Default settings
-#[derive(Debug, Clone, Deserialize, Serialize)]
+///
+/// Each value applies unless the option is set by a repository override, a
+/// `KODO_*` environment variable or a command-line flag.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Defaults {
     /// Number of days to analyze
-    #[serde(default = "default_days")]
-    pub days: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<u32>,
 
     /// Exclude merge commits
-    #[serde(default = "default_true")]
-    pub exclude_merges: bool,
-}
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_merges: Option<bool>,
 
-const fn default_days() -> u32 {
-    7
-}
+    /// Aggregation period
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<Period>,
+
+    /// First day of the week for weekly periods
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub week_start: Option<WeekStart>,
+
+    /// Label of weekly periods
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub week_label: Option<WeekLabel>,
+
+    /// Fixed-length periods instead of `period`, e.g. `"14d"` or `"2w"`
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bucket_length"
+    )]
+    pub bucket: Option<u32>,
+
+    /// Date one bucket starts on (YYYY-MM-DD or expression)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket_start: Option<String>,
+
+    /// Output format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<OutputFormat>,
+
+    /// Timezone for bucketing commits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tz: Option<Timezone>,
+
+    /// Commit date used for date ranges and bucketing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_field: Option<DateField>,
+
+    /// Metrics to report
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<Vec<MetricGroup>>,
+
+    /// Branches to analyze
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<Vec<String>>,
+
+    /// Analyze all local branches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all_branches: Option<bool>,
 
-const fn default_true() -> bool {
-    true
+    /// Ref globs to analyze
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refs: Option<Vec<String>>,
+
+    /// File extensions to include
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext: Option<Vec<String>>,
+
+    /// Globs of files to count (combined with each repository's own list)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+
+    /// Globs of files to skip (combined with each repository's own list)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude: Option<Vec<String>>,
+
+    /// Author filters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<Vec<String>>,
+
+    /// Include a per-author breakdown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_author: Option<bool>,
+
+    /// Follow only the first parent of merges
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_parent: Option<bool>,
+
+    /// Count generated and vendored files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_generated: Option<bool>,
+
+    /// Similarity threshold (0-100) for rename and copy detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename_threshold: Option<u16>,
+
+    /// Disable rename and copy detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_renames: Option<bool>,
+
+    /// Consecutive too-old commits that end the history walk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skew_slop: Option<u32>,
+
+    /// Worker threads for computing diffs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jobs: Option<usize>,
+
+    /// Analyze the checked-out submodules of every repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurse_submodules: Option<bool>,
+
+    /// Stop at the first repository that fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
 }
 
-impl Default for Defaults {
-    fn default() -> Self {
-        Self {
-            days: default_days(),
-            exclude_merges: default_true(),
+/// (De)serialize a bucket length in days as a string like `"14d"`
+mod bucket_length {
+    use crate::stats::period::parse_bucket;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    // serde's `with` requires `fn(&T, S)` signature
+    #[allow(clippy::ref_option, clippy::trivially_copy_pass_by_ref)]
+    pub fn serialize<S: Serializer>(days: &Option<u32>, serializer: S) -> Result<S::Ok, S::Error> {
+        match days {
+            Some(days) => serializer.collect_str(&format_args!("{days}d")),
+            None => serializer.serialize_none(),
         }
     }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u32>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| parse_bucket(&value).map_err(de::Error::custom))
+            .transpose()
+    }
 }
 
 #[cfg(test)]
@@ -66,9 +210,11 @@ mod tests {
 
     #[test]
     fn test_defaults() {
+        // Unset values fall back to the built-in CLI defaults
         let defaults = Defaults::default();
-        assert_eq!(defaults.days, 7);
-        assert!(defaults.exclude_merges);
+        assert_eq!(defaults.days, None);
+        assert_eq!(defaults.exclude_merges, None);
+        assert_eq!(serde_json::to_string(&defaults).unwrap(), "{}");
     }
 
     #[test]
@@ -87,7 +233,8 @@ mod tests {
         );
         assert_eq!(config.repositories.len(), 1);
         assert_eq!(config.repositories[0].name, "test-repo");
-        assert_eq!(config.defaults.days, 7);
+        assert_eq!(config.defaults.days, None);
+        assert!(config.mailmap.is_none());
     }
 
     #[test]
@@ -103,8 +250,76 @@ mod tests {
         }"#;
 
         let config: Config = serde_json::from_str(json).unwrap();
-        assert_eq!(config.defaults.days, 30);
-        assert!(!config.defaults.exclude_merges);
+        assert_eq!(config.defaults.days, Some(30));
+        assert_eq!(config.defaults.exclude_merges, Some(false));
+    }
+
+    #[test]
+    fn test_config_with_analysis_defaults() {
+        let json = r#"{
+            "repositories": [
+                {"name": "repo", "path": "/path", "ext": ["rs"], "first_parent": true}
+            ],
+            "defaults": {
+                "period": "weekly",
+                "week_start": "sunday",
+                "week_label": "start-date",
+                "bucket": "2w",
+                "strict": true,
+                "output": "json",
+                "tz": "Asia/Tokyo",
+                "date_field": "author",
+                "metrics": ["commits", "lines"],
+                "branch": ["main", "develop"],
+                "ext": ["rs", "toml"]
+            }
+        }"#;
+
+        let config: Config = serde_json::from_str(json).unwrap();
+        let defaults = &config.defaults;
+        assert_eq!(defaults.period, Some(Period::Weekly));
+        assert_eq!(defaults.week_start, Some(WeekStart::Sunday));
+        assert_eq!(defaults.week_label, Some(WeekLabel::StartDate));
+        assert_eq!(defaults.bucket, Some(14));
+        assert_eq!(defaults.strict, Some(true));
+        assert_eq!(
+            serde_json::to_value(defaults).unwrap()["bucket"],
+            serde_json::json!("14d")
+        );
+        assert!(serde_json::from_str::<Defaults>(r#"{"bucket": "fortnight"}"#).is_err());
+        assert_eq!(defaults.output, Some(OutputFormat::Json));
+        assert_eq!(defaults.tz, Some(Timezone::Named(chrono_tz::Asia::Tokyo)));
+        assert_eq!(defaults.date_field, Some(DateField::Author));
+        assert_eq!(
+            defaults.metrics,
+            Some(vec![MetricGroup::Commits, MetricGroup::Lines])
+        );
+        assert_eq!(
+            defaults.branch.as_deref(),
+            Some(&["main".to_string(), "develop".to_string()][..])
+        );
+        assert_eq!(config.repositories[0].ext, Some(vec!["rs".to_string()]));
+        assert_eq!(config.repositories[0].first_parent, Some(true));
+        assert_eq!(config.repositories[0].exclude_merges, None);
+
+        let invalid = json.replace("Asia/Tokyo", "Mars/Olympus");
+        assert!(serde_json::from_str::<Config>(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_config_with_mailmap() {
+        let json = r#"{
+            "repositories": [
+                {"name": "repo", "path": "/path"}
+            ],
+            "mailmap": "~/.config/kodo/mailmap"
+        }"#;
+
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.mailmap,
+            Some(PathBuf::from("~/.config/kodo/mailmap"))
+        );
     }
 
     #[test]
@@ -113,4 +328,32 @@ mod tests {
         let repo: RepoConfig = serde_json::from_str(json).unwrap();
         assert_eq!(repo.branch, Some("main".to_string()));
     }
+
+    #[test]
+    fn test_repo_config_with_path_globs() {
+        let json =
+            r#"{"name": "repo", "path": "/path", "include": ["src/"], "exclude": ["*.lock"]}"#;
+        let repo: RepoConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(repo.include, vec!["src/".to_string()]);
+        assert_eq!(repo.exclude, vec!["*.lock".to_string()]);
+
+        let json = r#"{"name": "repo", "path": "/path"}"#;
+        let repo: RepoConfig = serde_json::from_str(json).unwrap();
+        assert!(repo.include.is_empty());
+        let serialized = serde_json::to_string(&repo).unwrap();
+        assert!(!serialized.contains("include"));
+    }
+
+    #[test]
+    fn test_repo_config_recurse_submodules() {
+        let json = r#"{"name": "repo", "path": "/path", "recurse_submodules": true}"#;
+        let repo: RepoConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(repo.recurse_submodules, Some(true));
+
+        let json = r#"{"name": "repo", "path": "/path"}"#;
+        let repo: RepoConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(repo.recurse_submodules, None);
+        let serialized = serde_json::to_string(&repo).unwrap();
+        assert!(!serialized.contains("recurse_submodules"));
+    }
 }
@@ -1,6 +1,9 @@
 //! Error types for kodo
 
+use serde::Serialize;
+use std::fmt;
 use std::path::PathBuf;
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Main error type for kodo library
@@ -41,11 +44,104 @@ pub enum Error {
     /// Repository not found in configuration
     #[error("Repository not found in config: {identifier}")]
     RepoNotInConfig { identifier: String },
+
+    /// Date expression could not be parsed
+    #[error("Invalid date '{input}': {message}")]
+    InvalidDate { input: String, message: String },
+
+    /// Timezone is neither a keyword nor a known IANA name
+    #[error("Invalid timezone '{input}': expected local, utc, author or an IANA name")]
+    InvalidTimezone { input: String },
+
+    /// Start date is after end date
+    #[error("Invalid date range: {from} is after {to}")]
+    InvalidDateRange {
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    },
+
+    /// Branch, tag or revision could not be resolved
+    #[error("Unknown ref '{name}'{}", did_you_mean(.suggestions))]
+    UnknownRef {
+        name: String,
+        suggestions: Vec<String>,
+    },
+
+    /// Filter pattern could not be compiled
+    #[error("Invalid pattern '{pattern}': {message}")]
+    InvalidPattern { pattern: String, message: String },
+
+    /// Diff cache file could not be written
+    #[error("Could not save diff cache {path}: {message}")]
+    CacheWrite { path: PathBuf, message: String },
+
+    /// Worker thread pool could not be started
+    #[error("Failed to start worker threads: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
 }
 
 /// Result type alias using our Error type
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// A problem with one repository that did not stop the others from being analyzed
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoWarning {
+    /// Name of the failing repository
+    pub repository: String,
+
+    /// What went wrong (serialized as its message)
+    #[serde(serialize_with = "serialize_error")]
+    pub error: Arc<Error>,
+
+    /// Whether the repository was left out of the results
+    pub skipped: bool,
+}
+
+impl RepoWarning {
+    /// Create a warning for a repository that failed with the given error
+    #[must_use]
+    pub fn new(repository: String, error: Error) -> Self {
+        Self {
+            repository,
+            error: Arc::new(error),
+            skipped: true,
+        }
+    }
+
+    /// Create a warning for a repository whose results are still reported
+    #[must_use]
+    pub fn partial(repository: String, error: Error) -> Self {
+        Self {
+            skipped: false,
+            ..Self::new(repository, error)
+        }
+    }
+}
+
+impl fmt::Display for RepoWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.skipped {
+            write!(f, "skipped ")?;
+        }
+        write!(f, "repository '{}': {}", self.repository, self.error)
+    }
+}
+
+fn serialize_error<S>(error: &Arc<Error>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_str(error)
+}
+
+fn did_you_mean(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(" (did you mean: {}?)", suggestions.join(", "))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,4 +167,61 @@ mod tests {
         };
         assert!(err.to_string().contains("Not a git repository"));
     }
+
+    #[test]
+    fn test_error_invalid_pattern() {
+        let err = Error::InvalidPattern {
+            pattern: "(".to_string(),
+            message: "unclosed group".to_string(),
+        };
+        assert_eq!(err.to_string(), "Invalid pattern '(': unclosed group");
+    }
+
+    #[test]
+    fn test_error_unknown_ref() {
+        let err = Error::UnknownRef {
+            name: "mian".to_string(),
+            suggestions: vec!["main".to_string(), "origin/main".to_string()],
+        };
+        assert_eq!(
+            err.to_string(),
+            "Unknown ref 'mian' (did you mean: main, origin/main?)"
+        );
+
+        let err = Error::UnknownRef {
+            name: "xyz".to_string(),
+            suggestions: Vec::new(),
+        };
+        assert_eq!(err.to_string(), "Unknown ref 'xyz'");
+    }
+
+    #[test]
+    fn test_repo_warning_display_and_json() {
+        let warning = RepoWarning::new(
+            "api".to_string(),
+            Error::RepoNotFound {
+                path: PathBuf::from("/gone/api"),
+            },
+        );
+        assert_eq!(
+            warning.to_string(),
+            "skipped repository 'api': Repository not found: /gone/api"
+        );
+        assert_eq!(
+            serde_json::to_string(&warning).unwrap(),
+            r#"{"repository":"api","error":"Repository not found: /gone/api","skipped":true}"#
+        );
+
+        let warning = RepoWarning::partial(
+            "api".to_string(),
+            Error::CacheWrite {
+                path: PathBuf::from("/cache/api.json"),
+                message: "Permission denied".to_string(),
+            },
+        );
+        assert_eq!(
+            warning.to_string(),
+            "repository 'api': Could not save diff cache /cache/api.json: Permission denied"
+        );
+    }
 }
@@ -0,0 +1,128 @@
+//! `.gitattributes` as of a commit
+//!
+//! libgit2 reads attributes from the working tree, the index or HEAD, not from an
+//! arbitrary commit. To judge each commit by its own attribute files, the
+//! `.gitattributes` blobs of its tree are staged in an in-memory index that is
+//! installed on the repository handle and queried index-only. libgit2 still
+//! parses the files (including macros such as `binary`) and caches them by blob
+//! id, along with `info/attributes`.
+
+use crate::error::Result;
+use git2::{
+    AttrCheckFlags, AttrValue, ErrorCode, Index, IndexEntry, IndexTime, ObjectType,
+    Repository as Git2Repository, Tree,
+};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Attributes of a path that affect how its changes are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PathAttributes {
+    /// Marked `linguist-generated` or `linguist-vendored`
+    pub generated: bool,
+    /// Marked `-diff` (or `binary`), so treated as binary
+    pub no_diff: bool,
+}
+
+/// Attributes of the files in one commit's tree
+///
+/// Creating it replaces the index of the repository handle, so a handle should
+/// only be used for one commit's attributes at a time.
+pub(crate) struct CommitAttributes<'r> {
+    repo: &'r Git2Repository,
+    tree: Tree<'r>,
+    index: Index,
+    /// Directories whose `.gitattributes` has been looked up in the tree
+    staged: HashSet<String>,
+}
+
+impl<'r> CommitAttributes<'r> {
+    /// Attributes as of the given tree
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the in-memory index cannot be created
+    pub(crate) fn new(repo: &'r Git2Repository, tree: Tree<'r>) -> Result<Self> {
+        let mut index = Index::new()?;
+        repo.set_index(&mut index)?;
+
+        Ok(Self {
+            repo,
+            tree,
+            index,
+            staged: HashSet::new(),
+        })
+    }
+
+    /// Look up the attributes of a path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree or the attributes cannot be read
+    pub(crate) fn get(&mut self, path: &str) -> Result<PathAttributes> {
+        // Every directory from the root down to the file may hold a `.gitattributes`
+        let mut dir = String::new();
+        self.stage(&dir)?;
+        for component in path.split('/').take(path.matches('/').count()) {
+            if !dir.is_empty() {
+                dir.push('/');
+            }
+            dir.push_str(component);
+            self.stage(&dir)?;
+        }
+
+        let path = Path::new(path);
+        let value = |name: &str| -> Result<AttrValue<'_>> {
+            let value = self
+                .repo
+                .get_attr_bytes(path, name, AttrCheckFlags::INDEX_ONLY)?;
+            Ok(AttrValue::from_bytes(value))
+        };
+        let is_true = |value: AttrValue<'_>| {
+            matches!(value, AttrValue::True)
+                || matches!(value, AttrValue::String(s) if s.eq_ignore_ascii_case("true"))
+        };
+
+        Ok(PathAttributes {
+            generated: is_true(value("linguist-generated")?)
+                || is_true(value("linguist-vendored")?),
+            no_diff: matches!(value("diff")?, AttrValue::False),
+        })
+    }
+
+    /// Add the `.gitattributes` of a directory of the tree to the index, once
+    fn stage(&mut self, dir: &str) -> Result<()> {
+        if !self.staged.insert(dir.to_string()) {
+            return Ok(());
+        }
+
+        let path = if dir.is_empty() {
+            ".gitattributes".to_string()
+        } else {
+            format!("{dir}/.gitattributes")
+        };
+        let entry = match self.tree.get_path(Path::new(&path)) {
+            Ok(entry) if entry.kind() == Some(ObjectType::Blob) => entry,
+            Ok(_) => return Ok(()),
+            Err(e) if e.code() == ErrorCode::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        self.index.add(&IndexEntry {
+            ctime: IndexTime::new(0, 0),
+            mtime: IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode: u32::try_from(entry.filemode()).unwrap_or_default(),
+            uid: 0,
+            gid: 0,
+            file_size: 0,
+            id: entry.id(),
+            flags: 0,
+            flags_extended: 0,
+            path: path.into_bytes(),
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+//! On-disk cache of per-commit diff statistics
+//!
+//! Entries live under `<cache dir>/diffs/<repository>/<settings>.json`, keyed by
+//! commit OID. The `<settings>` part is a fingerprint of everything that changes
+//! how a diff is computed, so changing an option simply selects another file.
+
+use crate::error::{Error, Result};
+use crate::git::DiffStats;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Bump when the cached format or the diff computation changes
+const CACHE_VERSION: u32 = 5;
+
+/// Distinguishes temporary files of concurrent saves within this process
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Get the default cache directory (`$XDG_CACHE_HOME/kodo` or the platform equivalent)
+#[must_use]
+pub fn default_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|p| p.join("kodo"))
+}
+
+/// Cached diff statistics for one repository and one set of diff settings
+#[derive(Debug)]
+pub struct DiffCache {
+    path: PathBuf,
+    /// `None` for commits that change no path in the pathspec
+    entries: HashMap<String, Option<DiffStats>>,
+    dirty: bool,
+}
+
+impl DiffCache {
+    /// Load the cache file for a repository and settings fingerprint
+    ///
+    /// A missing or unreadable file yields an empty cache; it is rebuilt on save.
+    #[must_use]
+    pub fn load(cache_dir: &Path, repo_id: &str, settings: &str) -> Self {
+        let path = cache_dir
+            .join("diffs")
+            .join(fingerprint(repo_id))
+            .join(format!(
+                "{}.json",
+                fingerprint(&format!("v{CACHE_VERSION}:{settings}"))
+            ));
+
+        let entries = fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            entries,
+            dirty: false,
+        }
+    }
+
+    /// Get the cached statistics for a commit
+    ///
+    /// `Some(None)` means the commit is cached as changing no path in the pathspec.
+    #[must_use]
+    pub fn get(&self, oid: git2::Oid) -> Option<Option<&DiffStats>> {
+        self.entries.get(&oid.to_string()).map(Option::as_ref)
+    }
+
+    /// Store the statistics for a commit, `None` if it changes no path in the pathspec
+    pub fn insert(&mut self, oid: git2::Oid, stats: Option<DiffStats>) {
+        self.entries.insert(oid.to_string(), stats);
+        self.dirty = true;
+    }
+
+    /// Write the cache back to disk if anything was added
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory or file cannot be written
+    pub fn save(&self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        self.write().map_err(|e| Error::CacheWrite {
+            path: self.path.clone(),
+            message: e.to_string(),
+        })
+    }
+
+    fn write(&self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Write to a temporary file first so a concurrent reader never sees half a file
+        let tmp = self.path.with_extension(format!(
+            "json.{}.{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let written = fs::write(&tmp, serde_json::to_vec(&self.entries)?)
+            .and_then(|()| fs::rename(&tmp, &self.path));
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+
+        written
+    }
+}
+
+/// Summary of what is stored in the cache directory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of repositories with cached entries
+    pub repositories: usize,
+
+    /// Number of cached commits (counted once per settings fingerprint)
+    pub commits: usize,
+
+    /// Total size of the cache files in bytes
+    pub bytes: u64,
+}
+
+/// Summarize the contents of a cache directory
+///
+/// # Errors
+///
+/// Returns an error if the cache directory cannot be read
+pub fn cache_stats(cache_dir: &Path) -> Result<CacheStats> {
+    let mut stats = CacheStats::default();
+    let diffs = cache_dir.join("diffs");
+    if !diffs.exists() {
+        return Ok(stats);
+    }
+
+    for repo in fs::read_dir(diffs)? {
+        let repo = repo?;
+        if !repo.file_type()?.is_dir() {
+            continue;
+        }
+        stats.repositories += 1;
+
+        for file in fs::read_dir(repo.path())? {
+            let file = file?;
+            if file.path().extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            stats.bytes += file.metadata()?.len();
+            let entries: HashMap<String, serde_json::Value> = fs::read(file.path())
+                .ok()
+                .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+                .unwrap_or_default();
+            stats.commits += entries.len();
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Remove every cached entry
+///
+/// # Errors
+///
+/// Returns an error if the cache directory cannot be removed
+pub fn clear_cache(cache_dir: &Path) -> Result<()> {
+    let diffs = cache_dir.join("diffs");
+    if diffs.exists() {
+        fs::remove_dir_all(diffs)?;
+    }
+    Ok(())
+}
+
+/// Stable 64-bit FNV-1a hash as hex, used for cache file names
+fn fingerprint(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::FileChange;
+    use tempfile::TempDir;
+
+    fn oid(hex: &str) -> git2::Oid {
+        git2::Oid::from_str(hex).unwrap()
+    }
+
+    fn sample_stats() -> DiffStats {
+        let mut stats = DiffStats::default();
+        stats.add_file(FileChange::new("src/main.rs".to_string(), 10, 2));
+        stats
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let commit = oid("1234567890abcdef1234567890abcdef12345678");
+
+        let untouched = oid("abcdef1234567890abcdef1234567890abcdef12");
+
+        let mut cache = DiffCache::load(dir.path(), "/repo", "settings");
+        assert!(cache.get(commit).is_none());
+        cache.insert(commit, Some(sample_stats()));
+        cache.insert(untouched, None);
+        cache.save().unwrap();
+
+        let cache = DiffCache::load(dir.path(), "/repo", "settings");
+        assert_eq!(
+            cache.get(untouched).map(|stats| stats.is_none()),
+            Some(true)
+        );
+        let stats = cache.get(commit).flatten().unwrap();
+        assert_eq!(stats.additions, 10);
+        assert_eq!(stats.deletions, 2);
+        assert_eq!(stats.files[0].path, "src/main.rs");
+    }
+
+    #[test]
+    fn test_cache_keyed_by_repo_and_settings() {
+        let dir = TempDir::new().unwrap();
+        let commit = oid("1234567890abcdef1234567890abcdef12345678");
+
+        let mut cache = DiffCache::load(dir.path(), "/repo", "settings");
+        cache.insert(commit, Some(sample_stats()));
+        cache.save().unwrap();
+
+        assert!(
+            DiffCache::load(dir.path(), "/other", "settings")
+                .get(commit)
+                .is_none()
+        );
+        assert!(
+            DiffCache::load(dir.path(), "/repo", "changed")
+                .get(commit)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_concurrent_saves_do_not_clash() {
+        let dir = TempDir::new().unwrap();
+        let commit = oid("1234567890abcdef1234567890abcdef12345678");
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    let mut cache = DiffCache::load(dir.path(), "/repo", "settings");
+                    cache.insert(commit, Some(sample_stats()));
+                    cache.save().unwrap();
+                });
+            }
+        });
+
+        let cache = DiffCache::load(dir.path(), "/repo", "settings");
+        assert!(cache.get(commit).is_some());
+        let files = fs::read_dir(cache.path.parent().unwrap()).unwrap().count();
+        assert_eq!(files, 1);
+    }
+
+    #[test]
+    fn test_save_to_unwritable_dir_fails() {
+        let dir = TempDir::new().unwrap();
+        // A file where the cache directory should be
+        let blocked = dir.path().join("blocked");
+        fs::write(&blocked, "").unwrap();
+
+        let mut cache = DiffCache::load(&blocked, "/repo", "settings");
+        cache.insert(
+            oid("1234567890abcdef1234567890abcdef12345678"),
+            Some(sample_stats()),
+        );
+        assert!(cache.save().is_err());
+    }
+
+    #[test]
+    fn test_corrupt_cache_file_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        let cache = DiffCache::load(dir.path(), "/repo", "settings");
+        fs::create_dir_all(cache.path.parent().unwrap()).unwrap();
+        fs::write(&cache.path, "not json").unwrap();
+
+        let cache = DiffCache::load(dir.path(), "/repo", "settings");
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn test_cache_stats_and_clear() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(cache_stats(dir.path()).unwrap(), CacheStats::default());
+
+        for repo in ["/a", "/b"] {
+            let mut cache = DiffCache::load(dir.path(), repo, "settings");
+            cache.insert(
+                oid("1234567890abcdef1234567890abcdef12345678"),
+                Some(sample_stats()),
+            );
+            cache.insert(
+                oid("abcdef1234567890abcdef1234567890abcdef12"),
+                Some(sample_stats()),
+            );
+            cache.save().unwrap();
+        }
+
+        let stats = cache_stats(dir.path()).unwrap();
+        assert_eq!(stats.repositories, 2);
+        assert_eq!(stats.commits, 4);
+        assert!(stats.bytes > 0);
+
+        clear_cache(dir.path()).unwrap();
+        assert_eq!(cache_stats(dir.path()).unwrap(), CacheStats::default());
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        assert_eq!(fingerprint(""), "cbf29ce484222325");
+        assert_ne!(fingerprint("a"), fingerprint("b"));
+    }
+}
@@ -1,17 +1,32 @@
 //! Commit information types
 
-use crate::git::DiffStats;
-use chrono::{DateTime, Utc};
+use crate::git::{DiffStats, Timezone};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
 
 /// Extracted commit information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CommitInfo {
     /// Commit hash (short, 7 characters)
     pub id: String,
 
-    /// Commit timestamp (UTC)
+    /// Author name
+    pub author_name: String,
+
+    /// Author email
+    pub author_email: String,
+
+    /// Timestamp used for analysis (UTC), either the author or committer date
     pub timestamp: DateTime<Utc>,
 
+    /// UTC offset recorded with the timestamp, in minutes
+    pub offset_minutes: i32,
+
+    /// Author date with its recorded offset
+    pub author_date: DateTime<FixedOffset>,
+
+    /// Committer date with its recorded offset
+    pub committer_date: DateTime<FixedOffset>,
+
     /// Is this a merge commit?
     pub is_merge: bool,
 
@@ -28,13 +43,53 @@ impl CommitInfo {
             timestamp,
             is_merge,
             diff,
+            ..Default::default()
         }
     }
 
-    /// Get the date portion of the timestamp (UTC)
+    /// Set the author identity
+    #[must_use]
+    pub fn with_author(mut self, name: String, email: String) -> Self {
+        self.author_name = name;
+        self.author_email = email;
+        self
+    }
+
+    /// Author identity in `Name <email>` form (as shown by `git log`)
+    #[must_use]
+    pub fn author_signature(&self) -> String {
+        format!("{} <{}>", self.author_name, self.author_email)
+    }
+
+    /// Set the UTC offset recorded with the timestamp
+    #[must_use]
+    pub fn with_offset_minutes(mut self, offset_minutes: i32) -> Self {
+        self.offset_minutes = offset_minutes;
+        self
+    }
+
+    /// Record both the author and committer dates
+    #[must_use]
+    pub fn with_dates(
+        mut self,
+        author_date: DateTime<FixedOffset>,
+        committer_date: DateTime<FixedOffset>,
+    ) -> Self {
+        self.author_date = author_date;
+        self.committer_date = committer_date;
+        self
+    }
+
+    /// Wall-clock time of the commit in the given timezone
+    #[must_use]
+    pub fn local_time(&self, tz: Timezone) -> NaiveDateTime {
+        tz.wall_clock(self.timestamp, self.offset_minutes)
+    }
+
+    /// Calendar date of the commit in the given timezone
     #[must_use]
-    pub fn date(&self) -> chrono::NaiveDate {
-        self.timestamp.date_naive()
+    pub fn date(&self, tz: Timezone) -> NaiveDate {
+        self.local_time(tz).date()
     }
 }
 
@@ -53,7 +108,29 @@ mod tests {
             DiffStats::default(),
         );
 
-        assert_eq!(commit.date().to_string(), "2024-01-15");
+        assert_eq!(commit.date(Timezone::Utc).to_string(), "2024-01-15");
+    }
+
+    #[test]
+    fn test_commit_info_date_in_timezone() {
+        // 22:00 UTC is already the next day at +09:00
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 15, 22, 0, 0).unwrap();
+        let commit = CommitInfo::new(
+            "abc1234".to_string(),
+            timestamp,
+            false,
+            DiffStats::default(),
+        )
+        .with_offset_minutes(540);
+
+        assert_eq!(commit.date(Timezone::Utc).to_string(), "2024-01-15");
+        assert_eq!(commit.date(Timezone::Author).to_string(), "2024-01-16");
+        assert_eq!(
+            commit
+                .date(Timezone::Named(chrono_tz::America::New_York))
+                .to_string(),
+            "2024-01-15"
+        );
     }
 
     #[test]
@@ -63,4 +140,19 @@ mod tests {
 
         assert!(commit.is_merge);
     }
+
+    #[test]
+    fn test_commit_info_with_author() {
+        let commit = CommitInfo::new(
+            "abc1234".to_string(),
+            Utc::now(),
+            false,
+            DiffStats::default(),
+        )
+        .with_author("Alice".to_string(), "alice@example.com".to_string());
+
+        assert_eq!(commit.author_name, "Alice");
+        assert_eq!(commit.author_email, "alice@example.com");
+        assert_eq!(commit.author_signature(), "Alice <alice@example.com>");
+    }
 }
@@ -1,7 +1,37 @@
 //! Diff statistics types
 
+use serde::{Deserialize, Serialize};
+
+/// Default similarity threshold (percent) for rename/copy detection, same as git
+pub const DEFAULT_RENAME_THRESHOLD: u16 = 50;
+
+/// Settings that affect how diffs are computed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffConfig {
+    /// Similarity threshold (0-100) for rename/copy detection, `None` to disable
+    pub rename_threshold: Option<u16>,
+
+    /// Count files marked `linguist-generated` or `linguist-vendored` in
+    /// `.gitattributes` instead of skipping them
+    pub include_generated: bool,
+
+    /// Git pathspecs limiting diffs (and the commits walked) to matching paths;
+    /// empty means the whole tree
+    pub pathspec: Vec<String>,
+}
+
+impl Default for DiffConfig {
+    fn default() -> Self {
+        Self {
+            rename_threshold: Some(DEFAULT_RENAME_THRESHOLD),
+            include_generated: false,
+            pathspec: Vec::new(),
+        }
+    }
+}
+
 /// Diff statistics for a commit
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DiffStats {
     /// Total lines added
     pub additions: u64,
@@ -12,6 +42,12 @@ pub struct DiffStats {
     /// Number of files changed
     pub files_changed: u32,
 
+    /// Number of binary files changed
+    pub binary_files: u32,
+
+    /// Net bytes changed in binary files (sum of absolute size deltas)
+    pub binary_bytes: u64,
+
     /// Per-file changes
     pub files: Vec<FileChange>,
 }
@@ -24,7 +60,7 @@ impl DiffStats {
             additions,
             deletions,
             files_changed,
-            files: Vec::new(),
+            ..Default::default()
         }
     }
 
@@ -41,21 +77,59 @@ impl DiffStats {
         self.additions += file.additions;
         self.deletions += file.deletions;
         self.files_changed += 1;
+        if file.binary {
+            self.binary_files += 1;
+            self.binary_bytes += file.size_delta().unsigned_abs();
+        }
         self.files.push(file);
     }
+
+    /// Keep only files matching the predicate, recalculating totals
+    pub fn retain_files(&mut self, predicate: impl FnMut(&FileChange) -> bool) {
+        self.files.retain(predicate);
+        self.recalculate_totals();
+    }
+
+    /// Recalculate totals from the per-file changes
+    // File counts will never exceed u32::MAX in practice
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn recalculate_totals(&mut self) {
+        self.additions = self.files.iter().map(|f| f.additions).sum();
+        self.deletions = self.files.iter().map(|f| f.deletions).sum();
+        self.files_changed = self.files.len() as u32;
+        self.binary_files = self.files.iter().filter(|f| f.binary).count() as u32;
+        self.binary_bytes = self
+            .files
+            .iter()
+            .filter(|f| f.binary)
+            .map(|f| f.size_delta().unsigned_abs())
+            .sum();
+    }
 }
 
 /// Individual file change within a commit
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileChange {
     /// File path
     pub path: String,
 
+    /// Previous path if the file was renamed or copied
+    pub old_path: Option<String>,
+
     /// Lines added in this file
     pub additions: u64,
 
     /// Lines deleted in this file
     pub deletions: u64,
+
+    /// Whether git treats this file as binary (no line counts)
+    pub binary: bool,
+
+    /// Blob size in bytes before the change (0 if added)
+    pub old_size: u64,
+
+    /// Blob size in bytes after the change (0 if deleted)
+    pub new_size: u64,
 }
 
 impl FileChange {
@@ -64,11 +138,41 @@ impl FileChange {
     pub fn new(path: String, additions: u64, deletions: u64) -> Self {
         Self {
             path,
+            old_path: None,
             additions,
             deletions,
+            binary: false,
+            old_size: 0,
+            new_size: 0,
         }
     }
 
+    /// Create a `FileChange` for a binary file with its blob sizes
+    #[must_use]
+    pub fn binary(path: String, old_size: u64, new_size: u64) -> Self {
+        Self {
+            binary: true,
+            old_size,
+            new_size,
+            ..Self::new(path, 0, 0)
+        }
+    }
+
+    /// Size change in bytes (new - old)
+    #[must_use]
+    // File sizes will never exceed i64::MAX in practice
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn size_delta(&self) -> i64 {
+        self.new_size as i64 - self.old_size as i64
+    }
+
+    /// Set the previous path of a renamed or copied file
+    #[must_use]
+    pub fn with_old_path(mut self, old_path: String) -> Self {
+        self.old_path = Some(old_path);
+        self
+    }
+
     /// Check if file matches any of the given extensions
     #[must_use]
     pub fn matches_extensions(&self, extensions: &[String]) -> bool {
@@ -120,6 +224,59 @@ mod tests {
         assert!(file.matches_extensions(&[])); // Empty = match all
     }
 
+    #[test]
+    fn test_diff_stats_add_binary_file() {
+        let mut stats = DiffStats::default();
+        stats.add_file(FileChange::new("src/main.rs".to_string(), 10, 5));
+        stats.add_file(FileChange::binary("logo.png".to_string(), 1_000, 400));
+        stats.add_file(FileChange::binary("icon.png".to_string(), 0, 250));
+
+        assert_eq!(stats.additions, 10);
+        assert_eq!(stats.deletions, 5);
+        assert_eq!(stats.files_changed, 3);
+        assert_eq!(stats.binary_files, 2);
+        assert_eq!(stats.binary_bytes, 850);
+    }
+
+    #[test]
+    fn test_diff_stats_retain_files() {
+        let mut stats = DiffStats::default();
+        stats.add_file(FileChange::new("src/main.rs".to_string(), 10, 5));
+        stats.add_file(FileChange::new("Cargo.lock".to_string(), 200, 100));
+        stats.add_file(FileChange::binary("logo.png".to_string(), 0, 50));
+
+        stats.retain_files(|f| f.path != "Cargo.lock");
+
+        assert_eq!(stats.additions, 10);
+        assert_eq!(stats.deletions, 5);
+        assert_eq!(stats.files_changed, 2);
+        assert_eq!(stats.binary_files, 1);
+        assert_eq!(stats.binary_bytes, 50);
+    }
+
+    #[test]
+    fn test_file_change_size_delta() {
+        let file = FileChange::binary("logo.png".to_string(), 1_000, 400);
+        assert!(file.binary);
+        assert_eq!(file.size_delta(), -600);
+    }
+
+    #[test]
+    fn test_file_change_with_old_path() {
+        let file = FileChange::new("src/new.rs".to_string(), 1, 0);
+        assert!(file.old_path.is_none());
+
+        let file = file.with_old_path("src/old.rs".to_string());
+        assert_eq!(file.old_path.as_deref(), Some("src/old.rs"));
+    }
+
+    #[test]
+    fn test_diff_config_default_detects_renames() {
+        let config = DiffConfig::default();
+        assert_eq!(config.rename_threshold, Some(DEFAULT_RENAME_THRESHOLD));
+        assert!(!config.include_generated);
+    }
+
     #[test]
     fn test_file_change_no_extension() {
         let file = FileChange::new("Makefile".to_string(), 10, 5);
@@ -1,9 +1,14 @@
 //! Git repository interface module
 
+mod attributes;
+pub mod cache;
 pub mod commit;
 pub mod diff;
 pub mod repository;
+pub mod timezone;
 
+pub use cache::{CacheStats, DiffCache, cache_stats, clear_cache, default_cache_dir};
 pub use commit::CommitInfo;
-pub use diff::{DiffStats, FileChange};
-pub use repository::Repository;
+pub use diff::{DEFAULT_RENAME_THRESHOLD, DiffConfig, DiffStats, FileChange};
+pub use repository::{CommitWalk, DEFAULT_SKEW_SLOP, Repository};
+pub use timezone::{DateField, Timezone};
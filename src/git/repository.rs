@@ -4,15 +4,56 @@
 
 use crate::config::expand_tilde;
 use crate::error::{Error, Result};
-use crate::git::{CommitInfo, DiffStats, FileChange};
-use chrono::{DateTime, NaiveDate, TimeZone, Utc};
-use git2::{DiffOptions, Repository as Git2Repository};
-use std::path::Path;
+use crate::git::attributes::CommitAttributes;
+use crate::git::{CommitInfo, DateField, DiffCache, DiffConfig, DiffStats, FileChange, Timezone};
+use chrono::{DateTime, FixedOffset, NaiveDate, Offset, TimeZone, Utc};
+use git2::{
+    Delta, DiffFindOptions, DiffOptions, ErrorCode, Mailmap, Patch, Repository as Git2Repository,
+};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Default number of consecutive commits older than the date window after which
+/// a walk stops early (same idea as git's own `SLOP`)
+pub const DEFAULT_SKEW_SLOP: u32 = 5;
+
+/// Commits selected by a history walk
+#[derive(Debug, Default)]
+pub struct CommitWalk {
+    /// Commits inside the date window, newest first
+    pub commits: Vec<CommitInfo>,
+
+    /// Commits visited but skipped because their date is outside the window
+    pub out_of_range: usize,
+
+    /// Why the diff cache could not be written, if it could not
+    pub cache_error: Option<Error>,
+}
 
 /// Wrapper around `git2::Repository` with convenience methods
 pub struct Repository {
     inner: Git2Repository,
     name: String,
+    /// Additional mailmap applied after the repository's own `.mailmap`
+    global_mailmap: Option<Mailmap>,
+    /// Settings used when computing diffs
+    diff_config: DiffConfig,
+    /// Follow only the first parent of merge commits
+    first_parent: bool,
+    /// Timezone used to decide which day a commit falls on
+    timezone: Timezone,
+    /// Which commit date is used for analysis
+    date_field: DateField,
+    /// Consecutive too-old commits that end a walk early (0 walks everything)
+    skew_slop: u32,
+    /// Directory of the on-disk diff cache, `None` to always recompute diffs
+    cache_dir: Option<PathBuf>,
+    /// Compute diffs on the current rayon thread pool
+    parallel_diffs: bool,
+    /// Compute diff statistics at all (off when only commit counts are needed)
+    compute_diffs: bool,
 }
 
 impl Repository {
@@ -39,22 +80,144 @@ impl Repository {
         Ok(Self {
             inner,
             name: name.to_string(),
+            global_mailmap: None,
+            diff_config: DiffConfig::default(),
+            first_parent: false,
+            timezone: Timezone::default(),
+            date_field: DateField::default(),
+            skew_slop: DEFAULT_SKEW_SLOP,
+            cache_dir: None,
+            parallel_diffs: false,
+            compute_diffs: true,
         })
     }
 
+    /// Set the settings used when computing diffs
+    #[must_use]
+    pub fn with_diff_config(mut self, diff_config: DiffConfig) -> Self {
+        self.diff_config = diff_config;
+        self
+    }
+
+    /// Follow only the first parent of merge commits
+    ///
+    /// Commits brought in by a merge are not walked, so with merges included each
+    /// merge counts as one change carrying its full diff against the first parent.
+    #[must_use]
+    pub fn with_first_parent(mut self, first_parent: bool) -> Self {
+        self.first_parent = first_parent;
+        self
+    }
+
+    /// Set the timezone used to match commits against date windows
+    #[must_use]
+    pub fn with_timezone(mut self, timezone: Timezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Choose whether commits are dated by author or committer date
+    #[must_use]
+    pub fn with_date_field(mut self, date_field: DateField) -> Self {
+        self.date_field = date_field;
+        self
+    }
+
+    /// Set how many consecutive commits older than the date window end a walk
+    ///
+    /// Commits are walked by committer date, newest first. When analyzing author
+    /// dates, a commit made on a machine whose clock was behind sorts after older
+    /// commits although it was authored inside the window. Stopping only after
+    /// several old commits in a row keeps one such commit from being cut off.
+    /// Use 0 to always walk the full history.
+    #[must_use]
+    pub fn with_skew_slop(mut self, skew_slop: u32) -> Self {
+        self.skew_slop = skew_slop;
+        self
+    }
+
+    /// Reuse diff statistics cached under `cache_dir` and store newly computed ones
+    #[must_use]
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    /// Compute diffs in parallel on the current rayon thread pool
+    ///
+    /// The history walk itself stays sequential; only the diffs of the selected
+    /// commits are spread across workers.
+    #[must_use]
+    pub fn with_parallel_diffs(mut self, parallel_diffs: bool) -> Self {
+        self.parallel_diffs = parallel_diffs;
+        self
+    }
+
+    /// Choose whether diff statistics are computed
+    ///
+    /// Without diffs, commits carry empty `DiffStats`, which makes walks much
+    /// cheaper when only commit counts and activity are reported.
+    #[must_use]
+    pub fn with_diffs(mut self, compute_diffs: bool) -> Self {
+        self.compute_diffs = compute_diffs;
+        self
+    }
+
+    /// Apply an additional mailmap after the repository's own `.mailmap`
+    ///
+    /// Used to merge author identities that differ across repositories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mailmap contents cannot be parsed
+    pub fn with_global_mailmap(mut self, contents: &str) -> Result<Self> {
+        self.global_mailmap = Some(Mailmap::from_buffer(contents)?);
+        Ok(self)
+    }
+
     /// Get the repository name
     #[must_use]
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Checked-out submodules as `(path in this repository, working directory)`
+    ///
+    /// Submodules that are not initialized have no repository to analyze and are
+    /// left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `.gitmodules` cannot be read
+    pub fn submodules(&self) -> Result<Vec<(String, PathBuf)>> {
+        let Some(workdir) = self.inner.workdir() else {
+            return Ok(Vec::new());
+        };
+
+        Ok(self
+            .inner
+            .submodules()?
+            .iter()
+            .filter(|submodule| submodule.open().is_ok())
+            .map(|submodule| {
+                let path = submodule.path();
+                (path.to_string_lossy().to_string(), workdir.join(path))
+            })
+            .collect())
+    }
+
     /// Get commits in the specified date range
     ///
+    /// The walk starts from every given branch and every ref matching a glob, or
+    /// from HEAD when neither is given. Commits reachable from several starting
+    /// points are only returned once.
+    ///
     /// # Arguments
     ///
     /// * `from` - Start date (inclusive)
     /// * `to` - End date (inclusive)
-    /// * `branch` - Optional branch name (defaults to HEAD)
+    /// * `branches` - Branches, tags or revisions to start from
+    /// * `ref_globs` - Ref globs to start from (e.g. `refs/heads/*`)
     /// * `exclude_merges` - Whether to exclude merge commits
     ///
     /// # Errors
@@ -64,45 +227,156 @@ impl Repository {
         &self,
         from: NaiveDate,
         to: NaiveDate,
-        branch: Option<&str>,
+        branches: &[String],
+        ref_globs: &[String],
         exclude_merges: bool,
-    ) -> Result<Vec<CommitInfo>> {
+    ) -> Result<CommitWalk> {
         let mut revwalk = self.inner.revwalk()?;
 
-        // Start from the specified branch or HEAD
-        if let Some(branch_name) = branch {
-            let reference = self
-                .inner
-                .find_reference(&format!("refs/heads/{branch_name}"))?;
-            revwalk.push_ref(reference.name().unwrap_or("HEAD"))?;
-        } else {
+        for branch_name in branches {
+            revwalk.push(self.resolve_commit(branch_name)?)?;
+        }
+        for glob in ref_globs {
+            revwalk.push_glob(glob)?;
+        }
+        if branches.is_empty() && ref_globs.is_empty() {
             revwalk.push_head()?;
         }
 
+        self.collect_commits(revwalk, Some((from, to)), exclude_merges)
+    }
+
+    /// Get commits selected by a git revision spec
+    ///
+    /// Accepts anything `git rev-parse` understands: a single revision (all of its
+    /// ancestors), `A..B` (reachable from B but not A) or `A...B` (reachable from
+    /// either but not both). Tags are peeled to their commits.
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - Revision spec (e.g. `v1.2.0..v1.3.0`)
+    /// * `dates` - Optional (from, to) date window, both inclusive
+    /// * `exclude_merges` - Whether to exclude merge commits
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spec cannot be resolved or git operations fail
+    pub fn commits_in_revspec(
+        &self,
+        spec: &str,
+        dates: Option<(NaiveDate, NaiveDate)>,
+        exclude_merges: bool,
+    ) -> Result<CommitWalk> {
+        let revspec = self.inner.revparse(spec)?;
+        let mut revwalk = self.inner.revwalk()?;
+
+        let peel = |obj: Option<&git2::Object>| -> Result<Option<git2::Oid>> {
+            obj.map(|o| o.peel_to_commit().map(|c| c.id()))
+                .transpose()
+                .map_err(Error::from)
+        };
+        let from = peel(revspec.from())?;
+        let to = peel(revspec.to())?;
+
+        match (from, to) {
+            (Some(from), Some(to)) if revspec.mode().contains(git2::RevparseMode::MERGE_BASE) => {
+                revwalk.push(from)?;
+                revwalk.push(to)?;
+                // Criss-cross merges leave several merge bases; all of them are shared
+                let bases = match self.inner.merge_bases(from, to) {
+                    Ok(bases) => bases.iter().copied().collect(),
+                    // Unrelated histories share nothing
+                    Err(e) if e.code() == ErrorCode::NotFound => Vec::new(),
+                    Err(e) => return Err(e.into()),
+                };
+                for base in bases {
+                    revwalk.hide(base)?;
+                }
+            }
+            (Some(from), Some(to)) => {
+                revwalk.push(to)?;
+                revwalk.hide(from)?;
+            }
+            (Some(single), None) | (None, Some(single)) => revwalk.push(single)?,
+            (None, None) => revwalk.push_head()?,
+        }
+
+        self.collect_commits(revwalk, dates, exclude_merges)
+    }
+
+    /// Walk commits from a prepared revwalk, extracting info and diff stats
+    ///
+    /// If a date window is given, commits outside it are skipped and counted.
+    fn collect_commits(
+        &self,
+        mut revwalk: git2::Revwalk,
+        dates: Option<(NaiveDate, NaiveDate)>,
+        exclude_merges: bool,
+    ) -> Result<CommitWalk> {
         // Sort by time (newest first)
         revwalk.set_sorting(git2::Sort::TIME)?;
+        if self.first_parent {
+            revwalk.simplify_first_parent()?;
+        }
 
-        let from_datetime = Self::date_to_datetime(from);
-        let to_end = Self::date_to_datetime(to);
-        let to_datetime = to_end
-            .checked_add_signed(chrono::Duration::days(1))
-            .unwrap_or(to_end);
+        // UTC offsets stay within a day, so commits older than this are before
+        // `from` in every timezone
+        let cutoff = dates.map(|(from, _)| {
+            let from_utc = Self::date_to_datetime(from);
+            from_utc
+                .checked_sub_signed(chrono::Duration::days(1))
+                .unwrap_or(from_utc)
+        });
 
-        let mut commits = Vec::new();
+        let mailmap = self.inner.mailmap()?;
+        let mut cache = match &self.cache_dir {
+            Some(dir) if self.compute_diffs => Some(DiffCache::load(
+                dir,
+                &self.cache_id(),
+                &self.diff_settings()?,
+            )),
+            _ => None,
+        };
+        let mut walk = CommitWalk::default();
+        let mut oids = Vec::new();
+        let mut consecutive_old = 0;
 
         for oid_result in revwalk {
             let oid = oid_result?;
             let commit = self.inner.find_commit(oid)?;
 
-            // Convert git timestamp to DateTime<Utc>
-            let timestamp = Self::git_time_to_datetime(commit.time());
+            // Convert git timestamps to DateTime<Utc>
+            let author_time = commit.author().when();
+            let committer_time = commit.committer().when();
+            let time = match self.date_field {
+                DateField::Author => author_time,
+                DateField::Committer => committer_time,
+            };
+            let timestamp = Self::git_time_to_datetime(time);
 
             // Skip commits outside date range
-            if timestamp < from_datetime {
-                break; // Since we're sorted by time, no need to continue
-            }
-            if timestamp >= to_datetime {
-                continue;
+            if let (Some((from, to)), Some(cutoff)) = (dates, cutoff) {
+                let date = self
+                    .timezone
+                    .wall_clock(timestamp, time.offset_minutes())
+                    .date();
+                if date < from || date > to {
+                    walk.out_of_range += 1;
+
+                    // The walk is ordered by committer date, so only the committer date
+                    // can end it early. A skewed committer clock can still put commits
+                    // authored in the window behind old ones, so require a run of them.
+                    if Self::git_time_to_datetime(committer_time) < cutoff {
+                        consecutive_old += 1;
+                        if self.skew_slop > 0 && consecutive_old >= self.skew_slop {
+                            break;
+                        }
+                    } else {
+                        consecutive_old = 0;
+                    }
+                    continue;
+                }
+                consecutive_old = 0;
             }
 
             // Check if merge commit
@@ -111,24 +385,265 @@ impl Repository {
                 continue;
             }
 
-            // Calculate diff stats
-            let diff_stats = self.calculate_diff_stats(&commit)?;
+            // With a pathspec, only commits touching a matching path count. When
+            // diffs are computed, their pathspec-limited diff decides this instead.
+            if !self.compute_diffs && !self.touches_pathspec(&commit)? {
+                continue;
+            }
 
+            // Diffs are filled in after the walk
+            let (author_name, author_email) = self.resolve_author(&commit, &mailmap)?;
             let commit_info = CommitInfo::new(
                 oid.to_string()[..7].to_string(),
                 timestamp,
                 is_merge,
-                diff_stats,
+                DiffStats::default(),
+            )
+            .with_author(author_name, author_email)
+            .with_offset_minutes(time.offset_minutes())
+            .with_dates(
+                Self::git_time_to_fixed(author_time),
+                Self::git_time_to_fixed(committer_time),
             );
 
-            commits.push(commit_info);
+            walk.commits.push(commit_info);
+            oids.push(oid);
+        }
+
+        // Diffs dominate the cost, so they are computed separately from the walk
+        if self.compute_diffs {
+            let mut diffs = self.diff_stats_for(&oids, cache.as_mut())?.into_iter();
+            // Commits without a diff change no path in the pathspec
+            walk.commits
+                .retain_mut(|commit| match diffs.next().flatten() {
+                    Some(diff) => {
+                        commit.diff = diff;
+                        true
+                    }
+                    None => false,
+                });
+        }
+
+        // The cache only saves time, so failing to write it must not fail the walk
+        if let Some(cache) = &cache
+            && let Err(e) = cache.save()
+        {
+            walk.cache_error = Some(e);
         }
 
-        Ok(commits)
+        Ok(walk)
+    }
+
+    /// Diff statistics for each commit, in order
+    ///
+    /// Cached entries are reused; the rest are computed, on the current rayon
+    /// thread pool when parallel diffs are enabled. Each worker opens its own
+    /// `git2::Repository` handle since handles cannot be shared across threads.
+    fn diff_stats_for(
+        &self,
+        oids: &[git2::Oid],
+        mut cache: Option<&mut DiffCache>,
+    ) -> Result<Vec<Option<DiffStats>>> {
+        let missing: Vec<git2::Oid> = oids
+            .iter()
+            .filter(|&&oid| cache.as_deref().is_none_or(|c| c.get(oid).is_none()))
+            .copied()
+            .collect();
+
+        let diff_config = &self.diff_config;
+        let computed: Vec<Option<DiffStats>> = if self.parallel_diffs && missing.len() > 1 {
+            let path = self.inner.workdir().unwrap_or_else(|| self.inner.path());
+            // `map_init` opens a handle per split, so keep splits close to one per worker
+            let min_len = missing.len().div_ceil(rayon::current_num_threads() * 4);
+            missing
+                .par_iter()
+                .with_min_len(min_len)
+                .map_init(
+                    || Git2Repository::open(path),
+                    |repo, &oid| {
+                        let repo = repo
+                            .as_ref()
+                            .map_err(|e| git2::Error::new(e.code(), e.class(), e.message()))?;
+                        Self::calculate_diff_stats(repo, diff_config, &repo.find_commit(oid)?)
+                    },
+                )
+                .collect::<Result<_>>()?
+        } else {
+            missing
+                .iter()
+                .map(|&oid| {
+                    Self::calculate_diff_stats(
+                        &self.inner,
+                        diff_config,
+                        &self.inner.find_commit(oid)?,
+                    )
+                })
+                .collect::<Result<_>>()?
+        };
+
+        let mut computed: HashMap<git2::Oid, Option<DiffStats>> =
+            missing.into_iter().zip(computed).collect();
+        let mut stats = Vec::with_capacity(oids.len());
+        for oid in oids {
+            if let Some(diff) = computed.remove(oid) {
+                if let Some(cache) = cache.as_deref_mut() {
+                    cache.insert(*oid, diff.clone());
+                }
+                stats.push(diff);
+            } else if let Some(diff) = cache.as_deref().and_then(|c| c.get(*oid)) {
+                stats.push(diff.cloned());
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Identify the repository in the diff cache
+    fn cache_id(&self) -> String {
+        let path = self.inner.path();
+        path.canonicalize()
+            .unwrap_or_else(|_| path.to_path_buf())
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Describe everything that affects `calculate_diff_stats`
+    ///
+    /// Besides the diff settings this covers `info/attributes`, which applies to
+    /// every commit on top of its own `.gitattributes` files and so decides which
+    /// files count as generated or binary.
+    fn diff_settings(&self) -> Result<String> {
+        let DiffConfig {
+            rename_threshold,
+            include_generated,
+            pathspec,
+        } = &self.diff_config;
+        let renames = rename_threshold.map_or_else(|| "off".to_string(), |t| t.to_string());
+        let mut settings = format!(
+            "renames={renames};include_generated={include_generated};pathspec={}",
+            serde_json::to_string(pathspec)?
+        );
+        if let Ok(bytes) = std::fs::read(self.inner.path().join("info").join("attributes")) {
+            let oid = git2::Oid::hash_object(git2::ObjectType::Blob, &bytes)?;
+            let _ = write!(settings, "|info/attributes:{oid}");
+        }
+
+        Ok(settings)
+    }
+
+    /// Resolve the commit author through the repository and global mailmaps
+    fn resolve_author(&self, commit: &git2::Commit, mailmap: &Mailmap) -> Result<(String, String)> {
+        let mut author = commit.author_with_mailmap(mailmap)?;
+        if let Some(global) = &self.global_mailmap {
+            author = global.resolve_signature(&author)?;
+        }
+
+        Ok((
+            String::from_utf8_lossy(author.name_bytes()).to_string(),
+            String::from_utf8_lossy(author.email_bytes()).to_string(),
+        ))
     }
 
     /// Calculate diff statistics for a commit
-    fn calculate_diff_stats(&self, commit: &git2::Commit) -> Result<DiffStats> {
+    ///
+    /// Returns `None` if a pathspec is given and the commit changes no matching path.
+    fn calculate_diff_stats(
+        repo: &Git2Repository,
+        diff_config: &DiffConfig,
+        commit: &git2::Commit,
+    ) -> Result<Option<DiffStats>> {
+        let mut diff = Self::first_parent_diff(repo, diff_config, commit)?;
+        if !diff_config.pathspec.is_empty() && diff.deltas().len() == 0 {
+            return Ok(None);
+        }
+
+        // Detect renames/copies so moved files are not counted as delete + add
+        if let Some(threshold) = diff_config.rename_threshold {
+            let mut find_opts = DiffFindOptions::new();
+            find_opts
+                .renames(true)
+                .copies(true)
+                .rename_threshold(threshold)
+                .copy_threshold(threshold);
+            diff.find_similar(Some(&mut find_opts))?;
+        }
+
+        let mut stats = DiffStats::default();
+        // The commit's own attributes apply, so marking files later does not rewrite history
+        let mut attributes = CommitAttributes::new(repo, commit.tree()?)?;
+
+        // One patch per delta gives its line counts directly, without a callback
+        // (and a path lookup) for every changed line
+        for index in 0..diff.deltas().len() {
+            let Some(file_path) = diff.get_delta(index).and_then(|delta| {
+                let path = delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())?;
+                Some(path.to_string_lossy().to_string())
+            }) else {
+                continue;
+            };
+
+            // Generated and vendored code is not counted as human-written lines
+            let attrs = attributes.get(&file_path)?;
+            if attrs.generated && !diff_config.include_generated {
+                continue;
+            }
+
+            // `-diff` files are binary whatever their content, so skip loading them
+            let patch = if attrs.no_diff {
+                None
+            } else {
+                Patch::from_diff(&diff, index)?
+            };
+            // Loading the patch fills in the binary flag
+            let Some(delta) = patch
+                .as_ref()
+                .map(Patch::delta)
+                .or_else(|| diff.get_delta(index))
+            else {
+                continue;
+            };
+
+            let (additions, deletions) = match &patch {
+                Some(patch) => {
+                    let (_, additions, deletions) = patch.line_stats()?;
+                    (additions as u64, deletions as u64)
+                }
+                None => (0, 0),
+            };
+            let renamed = matches!(delta.status(), Delta::Renamed | Delta::Copied);
+            let binary = attrs.no_diff || delta.flags().is_binary();
+            // Mode-only changes have no lines and are not counted
+            if additions == 0 && deletions == 0 && !renamed && !binary {
+                continue;
+            }
+
+            let mut file = FileChange::new(file_path, additions, deletions);
+            if renamed && let Some(old_path) = delta.old_file().path() {
+                file = file.with_old_path(old_path.to_string_lossy().to_string());
+            }
+            if binary {
+                file.binary = true;
+                file.old_size = Self::blob_size(repo, delta.old_file().id())?;
+                file.new_size = Self::blob_size(repo, delta.new_file().id())?;
+            }
+            stats.files.push(file);
+        }
+
+        // Aggregate stats from files
+        stats.recalculate_totals();
+
+        Ok(Some(stats))
+    }
+
+    /// Tree diff of a commit against its first parent, limited to the pathspec
+    fn first_parent_diff<'r>(
+        repo: &'r Git2Repository,
+        diff_config: &DiffConfig,
+        commit: &git2::Commit,
+    ) -> Result<git2::Diff<'r>> {
         let tree = commit.tree()?;
 
         let parent_tree = if commit.parent_count() > 0 {
@@ -139,55 +654,71 @@ impl Repository {
 
         let mut diff_opts = DiffOptions::new();
         diff_opts.ignore_whitespace(false);
+        for spec in &diff_config.pathspec {
+            diff_opts.pathspec(spec);
+        }
 
-        let diff = self.inner.diff_tree_to_tree(
-            parent_tree.as_ref(),
-            Some(&tree),
-            Some(&mut diff_opts),
-        )?;
+        Ok(repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?)
+    }
 
-        let mut stats = DiffStats::default();
+    /// Whether a commit changes any path matching the pathspec
+    ///
+    /// Like the diff statistics, this compares against the first parent only. It
+    /// is only needed when no diffs are computed.
+    fn touches_pathspec(&self, commit: &git2::Commit) -> Result<bool> {
+        if self.diff_config.pathspec.is_empty() {
+            return Ok(true);
+        }
+        let diff = Self::first_parent_diff(&self.inner, &self.diff_config, commit)?;
+        Ok(diff.deltas().len() > 0)
+    }
 
-        diff.foreach(
-            &mut |_, _| true,
-            None,
-            None,
-            Some(&mut |delta, _hunk, line| {
-                let path = delta
-                    .new_file()
-                    .path()
-                    .or_else(|| delta.old_file().path())
-                    .map(|p| p.to_string_lossy().to_string())
-                    .unwrap_or_default();
-
-                match line.origin() {
-                    '+' => {
-                        // Find or create file entry
-                        if let Some(file) = stats.files.iter_mut().find(|f| f.path == path) {
-                            file.additions += 1;
-                        } else {
-                            stats.files.push(FileChange::new(path, 1, 0));
-                        }
-                    }
-                    '-' => {
-                        if let Some(file) = stats.files.iter_mut().find(|f| f.path == path) {
-                            file.deletions += 1;
-                        } else {
-                            stats.files.push(FileChange::new(path, 0, 1));
-                        }
-                    }
-                    _ => {}
-                }
-                true
-            }),
-        )?;
+    /// Resolve a branch, remote-tracking branch, tag or SHA to a commit
+    fn resolve_commit(&self, name: &str) -> Result<git2::Oid> {
+        match self.inner.revparse_single(name) {
+            Ok(object) => Ok(object.peel_to_commit()?.id()),
+            Err(e) if matches!(e.code(), ErrorCode::NotFound | ErrorCode::InvalidSpec) => {
+                Err(Error::UnknownRef {
+                    name: name.to_string(),
+                    suggestions: self.closest_refs(name)?,
+                })
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
 
-        // Aggregate stats from files
-        stats.additions = stats.files.iter().map(|f| f.additions).sum();
-        stats.deletions = stats.files.iter().map(|f| f.deletions).sum();
-        stats.files_changed = stats.files.len() as u32;
+    /// Short names of the refs closest to `name`, best match first
+    fn closest_refs(&self, name: &str) -> Result<Vec<String>> {
+        const MAX_SUGGESTIONS: usize = 5;
 
-        Ok(stats)
+        let max_distance = (name.len() / 3).max(2);
+        let mut candidates = Vec::new();
+        for reference in self.inner.references()? {
+            let reference = reference?;
+            let Some(short) = reference.shorthand() else {
+                continue;
+            };
+            let distance = edit_distance(name, short);
+            if distance <= max_distance || short.ends_with(&format!("/{name}")) {
+                candidates.push((distance, short.to_string()));
+            }
+        }
+
+        candidates.sort();
+        candidates.dedup_by(|a, b| a.1 == b.1);
+        Ok(candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, short)| short)
+            .collect())
+    }
+
+    /// Size of a blob in bytes (0 for the zero OID of added/deleted files)
+    fn blob_size(repo: &Git2Repository, oid: git2::Oid) -> Result<u64> {
+        if oid.is_zero() {
+            return Ok(0);
+        }
+        Ok(repo.find_blob(oid)?.size() as u64)
     }
 
     /// Convert `NaiveDate` to `DateTime<Utc>` at midnight
@@ -203,51 +734,42 @@ impl Repository {
     fn git_time_to_datetime(time: git2::Time) -> DateTime<Utc> {
         DateTime::from_timestamp(time.seconds(), 0).unwrap_or_else(Utc::now)
     }
+
+    /// Convert `git2::Time` to `DateTime<FixedOffset>`, keeping its recorded offset
+    fn git_time_to_fixed(time: git2::Time) -> DateTime<FixedOffset> {
+        let offset = FixedOffset::east_opt(time.offset_minutes() * 60).unwrap_or_else(|| Utc.fix());
+        Self::git_time_to_datetime(time).with_timezone(&offset)
+    }
+}
+
+/// Levenshtein distance between two strings, by character
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::process::Command;
+    use crate::test_support::{self, commit_as, commit_at, git};
     use tempfile::TempDir;
 
+    /// A repository with one commit, opened as "test-repo"
     fn create_test_repo() -> (TempDir, Repository) {
-        let dir = TempDir::new().unwrap();
-        let path = dir.path();
-
-        // Initialize git repo
-        Command::new("git")
-            .args(["init"])
-            .current_dir(path)
-            .output()
-            .unwrap();
-
-        // Configure git user
-        Command::new("git")
-            .args(["config", "user.email", "test@example.com"])
-            .current_dir(path)
-            .output()
-            .unwrap();
-        Command::new("git")
-            .args(["config", "user.name", "Test User"])
-            .current_dir(path)
-            .output()
-            .unwrap();
-
-        // Create initial commit
-        std::fs::write(path.join("README.md"), "# Test\n").unwrap();
-        Command::new("git")
-            .args(["add", "."])
-            .current_dir(path)
-            .output()
-            .unwrap();
-        Command::new("git")
-            .args(["commit", "-m", "Initial commit"])
-            .current_dir(path)
-            .output()
-            .unwrap();
-
-        let repo = Repository::open(path, "test-repo").unwrap();
+        let dir = test_support::create_test_repo();
+        let repo = Repository::open(dir.path(), "test-repo").unwrap();
         (dir, repo)
     }
 
@@ -277,12 +799,857 @@ mod tests {
         let today = Utc::now().date_naive();
         let from = today - chrono::Duration::days(7);
 
-        let commits = repo.commits_in_range(from, today, None, false).unwrap();
+        let commits = repo
+            .commits_in_range(from, today, &[], &[], false)
+            .unwrap()
+            .commits;
 
         // Should have at least the initial commit
         assert!(!commits.is_empty());
     }
 
+    #[test]
+    fn test_commits_in_range_multiple_branches() {
+        let (dir, repo) = create_test_repo();
+        commit_as(dir.path(), "main.txt", "Alice <alice@example.com>");
+        git(dir.path(), &["branch", "feature/a"]);
+        git(dir.path(), &["checkout", "-q", "-b", "feature/b"]);
+        commit_as(dir.path(), "b.txt", "Bob <bob@example.com>");
+        git(dir.path(), &["checkout", "-q", "feature/a"]);
+        commit_as(dir.path(), "a.txt", "Carol <carol@example.com>");
+
+        let today = Utc::now().date_naive();
+        let from = today - chrono::Duration::days(7);
+        let branches = vec!["feature/a".to_string(), "feature/b".to_string()];
+
+        // Initial + shared commit counted once, plus one per feature branch
+        let commits = repo
+            .commits_in_range(from, today, &branches, &[], false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 4);
+
+        let commits = repo
+            .commits_in_range(
+                from,
+                today,
+                &[],
+                &["refs/heads/feature/*".to_string()],
+                false,
+            )
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 4);
+
+        let commits = repo
+            .commits_in_range(from, today, &branches[..1], &[], false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 3);
+    }
+
+    #[test]
+    fn test_commits_in_range_resolves_tags_remotes_and_shas() {
+        let (dir, repo) = create_test_repo();
+        commit_as(dir.path(), "a.txt", "Alice <alice@example.com>");
+        git(dir.path(), &["tag", "v1.0.0"]);
+        git(
+            dir.path(),
+            &["update-ref", "refs/remotes/origin/main", "HEAD~1"],
+        );
+
+        let today = Utc::now().date_naive();
+        let from = today - chrono::Duration::days(7);
+        let count = |name: &str| {
+            repo.commits_in_range(from, today, &[name.to_string()], &[], false)
+                .unwrap()
+                .commits
+                .len()
+        };
+
+        assert_eq!(count("v1.0.0"), 2);
+        assert_eq!(count("origin/main"), 1);
+        assert_eq!(count("HEAD~1"), 1);
+        let sha = repo.inner.head().unwrap().target().unwrap().to_string();
+        assert_eq!(count(&sha), 2);
+    }
+
+    #[test]
+    fn test_commits_in_range_unknown_ref_suggests_closest() {
+        let (dir, repo) = create_test_repo();
+        git(dir.path(), &["branch", "develop"]);
+        git(
+            dir.path(),
+            &["update-ref", "refs/remotes/origin/release", "HEAD"],
+        );
+
+        let today = Utc::now().date_naive();
+        let from = today - chrono::Duration::days(7);
+
+        let result = repo.commits_in_range(from, today, &["devlop".to_string()], &[], false);
+        let Err(Error::UnknownRef { name, suggestions }) = result else {
+            panic!("expected UnknownRef, got {result:?}");
+        };
+        assert_eq!(name, "devlop");
+        assert_eq!(suggestions.first().map(String::as_str), Some("develop"));
+
+        let result = repo.commits_in_range(from, today, &["release".to_string()], &[], false);
+        let Err(Error::UnknownRef { suggestions, .. }) = result else {
+            panic!("expected UnknownRef, got {result:?}");
+        };
+        assert_eq!(suggestions, vec!["origin/release"]);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("main", "main"), 0);
+        assert_eq!(edit_distance("mian", "main"), 2);
+        assert_eq!(edit_distance("develop", "devlop"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_commits_in_range_captures_author() {
+        let (_dir, repo) = create_test_repo();
+
+        let today = Utc::now().date_naive();
+        let from = today - chrono::Duration::days(7);
+
+        let commits = repo
+            .commits_in_range(from, today, &[], &[], false)
+            .unwrap()
+            .commits;
+
+        assert_eq!(commits[0].author_name, "Test User");
+        assert_eq!(commits[0].author_email, "test@example.com");
+    }
+
+    #[test]
+    fn test_commits_in_range_applies_repo_mailmap() {
+        let (dir, repo) = create_test_repo();
+        std::fs::write(
+            dir.path().join(".mailmap"),
+            "Test User <test@example.com> <old@example.com>\n",
+        )
+        .unwrap();
+        commit_as(dir.path(), "a.txt", "Old Name <old@example.com>");
+
+        let today = Utc::now().date_naive();
+        let from = today - chrono::Duration::days(7);
+        let commits = repo
+            .commits_in_range(from, today, &[], &[], false)
+            .unwrap()
+            .commits;
+
+        assert!(commits.len() >= 2);
+        assert!(commits.iter().all(|c| c.author_name == "Test User"));
+        assert!(commits.iter().all(|c| c.author_email == "test@example.com"));
+    }
+
+    #[test]
+    fn test_commits_in_range_applies_global_mailmap() {
+        let (dir, repo) = create_test_repo();
+        commit_as(dir.path(), "a.txt", "Other <other@example.com>");
+
+        let repo = repo
+            .with_global_mailmap("Canonical <canonical@example.com> <other@example.com>\n")
+            .unwrap();
+
+        let today = Utc::now().date_naive();
+        let from = today - chrono::Duration::days(7);
+        let commits = repo
+            .commits_in_range(from, today, &[], &[], false)
+            .unwrap()
+            .commits;
+
+        assert!(
+            commits
+                .iter()
+                .any(|c| c.author_email == "canonical@example.com" && c.author_name == "Canonical")
+        );
+        assert!(commits.iter().any(|c| c.author_email == "test@example.com"));
+    }
+
+    #[test]
+    fn test_date_window_uses_timezone() {
+        let (dir, repo) = create_test_repo();
+        std::fs::write(dir.path().join("late.txt"), "late\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        // 23:30 in Tokyo is 14:30 UTC on the same day
+        commit_at(
+            dir.path(),
+            "Late night",
+            "2026-03-01T23:30:00+09:00",
+            "2026-03-01T23:30:00+09:00",
+        );
+
+        let count = |tz: Timezone, day: u32| {
+            let day = NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+            Repository::open(dir.path(), "test")
+                .unwrap()
+                .with_timezone(tz)
+                .commits_in_revspec("HEAD~1..HEAD", Some((day, day)), false)
+                .unwrap()
+                .commits
+                .len()
+        };
+
+        assert_eq!(count(Timezone::Author, 1), 1);
+        assert_eq!(count(Timezone::Utc, 1), 1);
+        // Auckland (+13:00 in March) is already on the next day
+        let auckland = Timezone::Named(chrono_tz::Pacific::Auckland);
+        assert_eq!(count(auckland, 1), 0);
+        assert_eq!(count(auckland, 2), 1);
+
+        let commits = repo
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits[0].offset_minutes, 540);
+    }
+
+    #[test]
+    fn test_date_field_selects_author_or_committer_date() {
+        let (dir, repo) = create_test_repo();
+        std::fs::write(dir.path().join("rebased.txt"), "rebased\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        // Written in January, rebased in March
+        commit_at(
+            dir.path(),
+            "Rebased",
+            "2026-01-05T10:00:00+01:00",
+            "2026-03-01T10:00:00+00:00",
+        );
+
+        let january = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        let march = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let count = |date_field: DateField, day: NaiveDate| {
+            Repository::open(dir.path(), "test")
+                .unwrap()
+                .with_timezone(Timezone::Utc)
+                .with_date_field(date_field)
+                .commits_in_revspec("HEAD~1..HEAD", Some((day, day)), false)
+                .unwrap()
+                .commits
+                .len()
+        };
+
+        assert_eq!(count(DateField::Committer, march), 1);
+        assert_eq!(count(DateField::Committer, january), 0);
+        assert_eq!(count(DateField::Author, january), 1);
+        assert_eq!(count(DateField::Author, march), 0);
+
+        let commits = repo
+            .with_date_field(DateField::Author)
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        let commit = &commits[0];
+        assert_eq!(commit.date(Timezone::Utc), january);
+        assert_eq!(commit.offset_minutes, 60);
+        assert_eq!(commit.author_date.to_rfc3339(), "2026-01-05T10:00:00+01:00");
+        assert_eq!(
+            commit.committer_date.to_rfc3339(),
+            "2026-03-01T10:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_skew_slop_bounds_early_exit() {
+        let (dir, repo) = create_test_repo();
+        for year in ["2001", "2002", "2003"] {
+            std::fs::write(dir.path().join(year), year).unwrap();
+            git(dir.path(), &["add", "."]);
+            let date = format!("{year}-01-01T12:00:00+00:00");
+            commit_at(dir.path(), year, &date, &date);
+        }
+
+        let today = Utc::now().date_naive();
+        let from = today - chrono::Duration::days(7);
+
+        let walk = repo.commits_in_range(from, today, &[], &[], false).unwrap();
+        assert_eq!(walk.commits.len(), 1);
+        assert_eq!(walk.out_of_range, 3);
+
+        let walk = repo
+            .with_skew_slop(2)
+            .commits_in_range(from, today, &[], &[], false)
+            .unwrap();
+        assert_eq!(walk.commits.len(), 1);
+        assert_eq!(walk.out_of_range, 2);
+    }
+
+    #[test]
+    fn test_commits_in_revspec_range() {
+        let (dir, repo) = create_test_repo();
+        git(dir.path(), &["tag", "v1.0.0"]);
+        commit_as(dir.path(), "a.txt", "Test User <test@example.com>");
+        commit_as(dir.path(), "b.txt", "Test User <test@example.com>");
+        git(dir.path(), &["tag", "-a", "v1.1.0", "-m", "release"]);
+        commit_as(dir.path(), "c.txt", "Test User <test@example.com>");
+
+        let commits = repo
+            .commits_in_revspec("v1.0.0..v1.1.0", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 2);
+
+        let commits = repo
+            .commits_in_revspec("v1.1.0", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 3);
+    }
+
+    #[test]
+    fn test_commits_in_revspec_symmetric_difference() {
+        let (dir, repo) = create_test_repo();
+        git(dir.path(), &["branch", "side"]);
+        commit_as(dir.path(), "a.txt", "Test User <test@example.com>");
+        git(dir.path(), &["checkout", "-q", "side"]);
+        commit_as(dir.path(), "b.txt", "Test User <test@example.com>");
+        commit_as(dir.path(), "c.txt", "Test User <test@example.com>");
+        git(dir.path(), &["checkout", "-q", "-"]);
+
+        let commits = repo
+            .commits_in_revspec("HEAD...side", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 3);
+
+        let commits = repo
+            .commits_in_revspec("HEAD..side", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 2);
+    }
+
+    #[test]
+    fn test_commits_in_revspec_symmetric_difference_with_criss_cross() {
+        let (dir, repo) = create_test_repo();
+        let author = "Test User <test@example.com>";
+        git(dir.path(), &["checkout", "-q", "-b", "left"]);
+        commit_as(dir.path(), "a.txt", author);
+        git(dir.path(), &["checkout", "-q", "-b", "right", "HEAD~1"]);
+        commit_as(dir.path(), "b.txt", author);
+
+        // Merge each side into the other, so both tips have two merge bases
+        git(dir.path(), &["merge", "-q", "--no-ff", "--no-edit", "left"]);
+        git(dir.path(), &["checkout", "-q", "left"]);
+        git(
+            dir.path(),
+            &["merge", "-q", "--no-ff", "--no-edit", "right~1"],
+        );
+        commit_as(dir.path(), "c.txt", author);
+        git(dir.path(), &["checkout", "-q", "right"]);
+        commit_as(dir.path(), "d.txt", author);
+
+        let bases = repo.inner.merge_bases(
+            repo.resolve_commit("left").unwrap(),
+            repo.resolve_commit("right").unwrap(),
+        );
+        assert_eq!(bases.unwrap().len(), 2);
+
+        // Both merges and both tips, like `git rev-list --count left...right`
+        let commits = repo
+            .commits_in_revspec("left...right", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 4);
+    }
+
+    #[test]
+    fn test_commits_in_revspec_with_date_window() {
+        let (_dir, repo) = create_test_repo();
+        let long_ago = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+
+        let commits = repo
+            .commits_in_revspec("HEAD", Some((long_ago, long_ago)), false)
+            .unwrap()
+            .commits;
+        assert!(commits.is_empty());
+    }
+
+    #[test]
+    fn test_commits_in_revspec_unknown_rev() {
+        let (_dir, repo) = create_test_repo();
+        let result = repo.commits_in_revspec("no-such-tag..HEAD", None, false);
+        assert!(matches!(result, Err(Error::Git(_))));
+    }
+
+    /// Content with enough distinct lines for similarity detection
+    fn numbered_lines(count: usize) -> String {
+        (0..count)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n"
+    }
+
+    #[test]
+    fn test_first_parent_counts_merge_as_one_change() {
+        let (dir, repo) = create_test_repo();
+        git(dir.path(), &["checkout", "-q", "-b", "feature"]);
+        std::fs::write(dir.path().join("a.txt"), numbered_lines(3)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Feature part 1"]);
+        std::fs::write(dir.path().join("b.txt"), numbered_lines(4)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Feature part 2"]);
+        git(dir.path(), &["checkout", "-q", "-"]);
+        git(
+            dir.path(),
+            &["merge", "-q", "--no-ff", "-m", "Merge feature", "feature"],
+        );
+
+        // Default walk visits the feature commits individually
+        let commits = repo
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 3);
+
+        let repo = repo.with_first_parent(true);
+        let commits = repo
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 1);
+        assert!(commits[0].is_merge);
+        assert_eq!(commits[0].diff.additions, 7);
+        assert_eq!(commits[0].diff.files_changed, 2);
+    }
+
+    #[test]
+    fn test_rename_detection() {
+        let (dir, repo) = create_test_repo();
+        std::fs::write(dir.path().join("old.txt"), numbered_lines(100)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Add file"]);
+
+        // Move the file and change one line
+        std::fs::remove_file(dir.path().join("old.txt")).unwrap();
+        let content = numbered_lines(100).replace("line 5\n", "line five\n");
+        std::fs::write(dir.path().join("new.txt"), content).unwrap();
+        git(dir.path(), &["add", "-A"]);
+        git(dir.path(), &["commit", "-m", "Move file"]);
+
+        let commits = repo
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        let diff = &commits[0].diff;
+        assert_eq!(diff.additions, 1);
+        assert_eq!(diff.deletions, 1);
+        assert_eq!(diff.files_changed, 1);
+        assert_eq!(diff.files[0].path, "new.txt");
+        assert_eq!(diff.files[0].old_path.as_deref(), Some("old.txt"));
+
+        // Without rename detection the move is a full delete + add
+        let repo = repo.with_diff_config(DiffConfig {
+            rename_threshold: None,
+            ..Default::default()
+        });
+        let commits = repo
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        let diff = &commits[0].diff;
+        assert_eq!(diff.additions, 100);
+        assert_eq!(diff.deletions, 100);
+        assert_eq!(diff.files_changed, 2);
+        assert!(diff.files.iter().all(|f| f.old_path.is_none()));
+    }
+
+    #[test]
+    fn test_pure_rename_counts_as_file_change() {
+        let (dir, repo) = create_test_repo();
+        git(dir.path(), &["mv", "README.md", "README.txt"]);
+        git(dir.path(), &["commit", "-m", "Rename"]);
+
+        let commits = repo
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        let diff = &commits[0].diff;
+        assert_eq!(diff.additions, 0);
+        assert_eq!(diff.deletions, 0);
+        assert_eq!(diff.files_changed, 1);
+        assert_eq!(diff.files[0].old_path.as_deref(), Some("README.md"));
+    }
+
+    #[test]
+    fn test_generated_files_skipped_via_gitattributes() {
+        let (dir, repo) = create_test_repo();
+        std::fs::write(
+            dir.path().join(".gitattributes"),
+            "gen/** linguist-generated\nvendor/** linguist-vendored=true\n*.min.js -diff\n\
+             *.dat binary\n",
+        )
+        .unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Add attributes"]);
+
+        for dir_name in ["gen", "vendor", "src"] {
+            std::fs::create_dir(dir.path().join(dir_name)).unwrap();
+        }
+        std::fs::write(dir.path().join("gen/api.pb.rs"), numbered_lines(50)).unwrap();
+        std::fs::write(dir.path().join("vendor/lib.c"), numbered_lines(30)).unwrap();
+        std::fs::write(dir.path().join("src/app.min.js"), numbered_lines(20)).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), numbered_lines(5)).unwrap();
+        std::fs::write(dir.path().join("src/table.dat"), numbered_lines(3)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Add code"]);
+
+        let commits = repo
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        // Generated and vendored files are skipped, -diff and binary files count as binary
+        let diff = &commits[0].diff;
+        assert_eq!(diff.files_changed, 3);
+        assert_eq!(diff.additions, 5);
+        assert_eq!(diff.binary_files, 2);
+        let mut binary: Vec<_> = diff
+            .files
+            .iter()
+            .filter(|f| f.binary)
+            .map(|f| (f.path.as_str(), f.new_size))
+            .collect();
+        binary.sort_unstable();
+        assert_eq!(
+            binary,
+            [
+                ("src/app.min.js", numbered_lines(20).len() as u64),
+                ("src/table.dat", numbered_lines(3).len() as u64)
+            ]
+        );
+
+        let repo = repo.with_diff_config(DiffConfig {
+            include_generated: true,
+            ..Default::default()
+        });
+        let commits = repo
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        let diff = &commits[0].diff;
+        assert_eq!(diff.files_changed, 5);
+        assert_eq!(diff.additions, 85);
+        assert_eq!(diff.binary_files, 2);
+    }
+
+    #[test]
+    fn test_gitattributes_read_from_each_commit() {
+        let (dir, repo) = create_test_repo();
+        std::fs::create_dir(dir.path().join("gen")).unwrap();
+        std::fs::write(dir.path().join("gen/api.rs"), numbered_lines(4)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Add hand-written api"]);
+
+        std::fs::write(
+            dir.path().join("gen/.gitattributes"),
+            "*.rs linguist-generated\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("gen/api.rs"), numbered_lines(10)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Generate api"]);
+
+        // Uncommitted attributes affect no commit
+        std::fs::write(dir.path().join(".gitattributes"), "* linguist-generated\n").unwrap();
+
+        let commits = repo
+            .commits_in_revspec("HEAD~2..HEAD", None, false)
+            .unwrap()
+            .commits;
+
+        // Newest first: the generated rewrite is skipped, the earlier commit still counts
+        assert_eq!(commits[0].diff.files_changed, 1);
+        assert_eq!(commits[0].diff.files[0].path, "gen/.gitattributes");
+        assert_eq!(commits[1].diff.files_changed, 1);
+        assert_eq!(commits[1].diff.additions, 4);
+    }
+
+    #[test]
+    fn test_diff_cache_reused_and_invalidated() {
+        let (dir, repo) = create_test_repo();
+        let cache_dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("main.rs"), numbered_lines(5)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Add code"]);
+
+        let repo = repo.with_cache_dir(cache_dir.path().to_path_buf());
+        let additions = |repo: &Repository| {
+            repo.commits_in_revspec("HEAD~1..HEAD", None, false)
+                .unwrap()
+                .commits[0]
+                .diff
+                .additions
+        };
+
+        assert_eq!(additions(&repo), 5);
+        assert_eq!(
+            crate::git::cache_stats(cache_dir.path()).unwrap().commits,
+            1
+        );
+        assert_eq!(additions(&repo), 5);
+        assert_eq!(
+            crate::git::cache_stats(cache_dir.path()).unwrap().commits,
+            1
+        );
+
+        // Marking the file as generated changes the result, so the entry is not reused
+        std::fs::write(
+            dir.path().join(".git/info/attributes"),
+            "*.rs linguist-generated\n",
+        )
+        .unwrap();
+        assert_eq!(additions(&repo), 0);
+
+        // Different diff settings get their own entries
+        let repo = repo.with_diff_config(DiffConfig {
+            include_generated: true,
+            ..Default::default()
+        });
+        assert_eq!(additions(&repo), 5);
+        assert_eq!(
+            crate::git::cache_stats(cache_dir.path()).unwrap().commits,
+            3
+        );
+    }
+
+    #[test]
+    fn test_parallel_diffs_match_serial() {
+        let (dir, repo) = create_test_repo();
+        for i in 1..=6 {
+            std::fs::write(dir.path().join(format!("file{i}.txt")), numbered_lines(i)).unwrap();
+            git(dir.path(), &["add", "."]);
+            git(dir.path(), &["commit", "-m", &format!("Commit {i}")]);
+        }
+
+        let summary = |walk: CommitWalk| -> Vec<(String, u64, u32)> {
+            walk.commits
+                .into_iter()
+                .map(|c| (c.id, c.diff.additions, c.diff.files_changed))
+                .collect()
+        };
+        let serial = summary(repo.commits_in_revspec("HEAD", None, false).unwrap());
+
+        // Repository handles are not `Sync`, so open one inside the pool
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let parallel = summary(pool.install(|| {
+            Repository::open(dir.path(), "test-repo")
+                .unwrap()
+                .with_parallel_diffs(true)
+                .commits_in_revspec("HEAD", None, false)
+                .unwrap()
+        }));
+
+        assert_eq!(serial.len(), 7);
+        assert_eq!(parallel, serial);
+        assert_eq!(parallel[0].1, 6);
+    }
+
+    #[test]
+    fn test_per_file_counts_in_large_commit() {
+        let (dir, repo) = create_test_repo();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        for i in 1..=200 {
+            std::fs::write(
+                dir.path().join(format!("src/file{i}.txt")),
+                numbered_lines(i),
+            )
+            .unwrap();
+        }
+        std::fs::write(dir.path().join("README.md"), "# Changed\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Add many files"]);
+
+        let commits = repo
+            .commits_in_revspec("HEAD~1..HEAD", None, false)
+            .unwrap()
+            .commits;
+        let diff = &commits[0].diff;
+        assert_eq!(diff.files_changed, 201);
+        assert_eq!(diff.additions, (1..=200).sum::<u64>() + 1);
+        assert_eq!(diff.deletions, 1);
+
+        let file = |path: &str| diff.files.iter().find(|f| f.path == path).unwrap();
+        assert_eq!(file("src/file37.txt").additions, 37);
+        assert_eq!(file("src/file37.txt").deletions, 0);
+        assert_eq!(file("README.md").additions, 1);
+        assert_eq!(file("README.md").deletions, 1);
+    }
+
+    #[test]
+    fn test_without_diffs_commits_have_empty_stats() {
+        let (dir, repo) = create_test_repo();
+        std::fs::write(dir.path().join("main.rs"), numbered_lines(5)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Add code"]);
+
+        let commits = repo
+            .with_diffs(false)
+            .commits_in_revspec("HEAD", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 2);
+        assert!(commits.iter().all(|c| c.diff.files.is_empty()));
+        assert_eq!(commits[0].diff.additions, 0);
+    }
+
+    #[test]
+    fn test_pathspec_limits_commits_and_lines() {
+        let (dir, repo) = create_test_repo();
+        for sub in ["services/api", "services/web"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        std::fs::write(dir.path().join("services/api/main.rs"), numbered_lines(4)).unwrap();
+        std::fs::write(dir.path().join("services/web/app.js"), numbered_lines(7)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Touch both services"]);
+        std::fs::write(dir.path().join("services/web/app.js"), numbered_lines(9)).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Touch web only"]);
+
+        let repo = repo.with_diff_config(DiffConfig {
+            pathspec: vec!["services/api".to_string()],
+            ..Default::default()
+        });
+        let commits = repo
+            .commits_in_revspec("HEAD", None, false)
+            .unwrap()
+            .commits;
+
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].diff.files_changed, 1);
+        assert_eq!(commits[0].diff.additions, 4);
+        assert_eq!(commits[0].diff.files[0].path, "services/api/main.rs");
+
+        // Commits outside the pathspec are remembered by the cache as well
+        let cache_dir = TempDir::new().unwrap();
+        let repo = repo.with_cache_dir(cache_dir.path().to_path_buf());
+        for _ in 0..2 {
+            let commits = repo
+                .commits_in_revspec("HEAD", None, false)
+                .unwrap()
+                .commits;
+            assert_eq!(commits.len(), 1);
+            assert_eq!(commits[0].diff.additions, 4);
+        }
+        assert_eq!(
+            crate::git::cache_stats(cache_dir.path()).unwrap().commits,
+            3
+        );
+
+        // Commit selection does not depend on computing diffs
+        let commits = repo
+            .with_diffs(false)
+            .commits_in_revspec("HEAD", None, false)
+            .unwrap()
+            .commits;
+        assert_eq!(commits.len(), 1);
+    }
+
+    #[test]
+    fn test_submodules_lists_checked_out_submodules() {
+        let (dir, repo) = create_test_repo();
+        assert!(repo.submodules().unwrap().is_empty());
+
+        let (sub_dir, _sub) = create_test_repo();
+        git(
+            dir.path(),
+            &[
+                "-c",
+                "protocol.file.allow=always",
+                "submodule",
+                "add",
+                "-q",
+                sub_dir.path().to_str().unwrap(),
+                "libs/core",
+            ],
+        );
+        git(dir.path(), &["commit", "-m", "Add submodule"]);
+
+        let submodules = repo.submodules().unwrap();
+        assert_eq!(submodules.len(), 1);
+        assert_eq!(submodules[0].0, "libs/core");
+        assert_eq!(submodules[0].1, dir.path().join("libs/core"));
+
+        let sub = Repository::open(&submodules[0].1, "core").unwrap();
+        let commits = sub.commits_in_revspec("HEAD", None, false).unwrap().commits;
+        assert_eq!(commits.len(), 1);
+    }
+
+    #[test]
+    fn test_binary_files_tracked_separately() {
+        let (dir, repo) = create_test_repo();
+        std::fs::write(dir.path().join("logo.png"), [0u8, 1, 2, 3, 0, 255, 0, 7]).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Add logo"]);
+
+        std::fs::write(dir.path().join("logo.png"), [0u8; 20]).unwrap();
+        std::fs::write(dir.path().join("README.md"), "# Test\nMore\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Swap logo"]);
+
+        let commits = repo
+            .commits_in_revspec("HEAD~2..HEAD", None, false)
+            .unwrap()
+            .commits;
+
+        // Newest first: swap, then add
+        let swap = &commits[0].diff;
+        assert_eq!(swap.files_changed, 2);
+        assert_eq!(swap.additions, 1);
+        assert_eq!(swap.binary_files, 1);
+        assert_eq!(swap.binary_bytes, 12);
+        let logo = swap.files.iter().find(|f| f.path == "logo.png").unwrap();
+        assert!(logo.binary);
+        assert_eq!((logo.old_size, logo.new_size), (8, 20));
+
+        let add = &commits[1].diff;
+        assert_eq!(add.files_changed, 1);
+        assert_eq!(add.additions, 0);
+        assert_eq!(add.binary_files, 1);
+        assert_eq!(add.binary_bytes, 8);
+    }
+
+    #[test]
+    fn test_binary_same_size_swap_counts_as_binary_file() {
+        let (dir, repo) = create_test_repo();
+        std::fs::write(dir.path().join("logo.png"), [0u8, 1, 2, 3, 0, 255, 0, 7]).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Add logo"]);
+
+        std::fs::write(dir.path().join("logo.png"), [0u8, 9, 9, 9, 0, 255, 0, 1]).unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "Swap logo"]);
+
+        std::fs::create_dir(dir.path().join("img")).unwrap();
+        git(dir.path(), &["mv", "logo.png", "img/logo.png"]);
+        git(dir.path(), &["commit", "-m", "Move logo"]);
+
+        let commits = repo
+            .commits_in_revspec("HEAD~2..HEAD", None, false)
+            .unwrap()
+            .commits;
+
+        // Newest first: move, then swap
+        let moved = &commits[0].diff;
+        assert_eq!(moved.binary_files, 1);
+        assert_eq!(moved.binary_bytes, 0);
+
+        // No net size change, but the swap still counts as a changed binary file
+        let swap = &commits[1].diff;
+        assert_eq!(swap.binary_files, 1);
+        assert_eq!(swap.binary_bytes, 0);
+    }
+
     #[test]
     fn test_date_to_datetime() {
         use chrono::Timelike;
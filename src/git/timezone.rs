@@ -0,0 +1,186 @@
+//! Which commit timestamp, in which timezone, is turned into a calendar date
+
+use crate::error::{Error, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Timezone in which commits are bucketed into days, weekdays and hours
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Timezone {
+    /// System local timezone
+    #[default]
+    Local,
+    /// Coordinated Universal Time
+    Utc,
+    /// IANA timezone (e.g. `Asia/Tokyo`)
+    Named(chrono_tz::Tz),
+    /// Each commit's own recorded UTC offset
+    Author,
+}
+
+impl Timezone {
+    /// Wall-clock time of `timestamp` in this timezone
+    ///
+    /// `offset_minutes` is the UTC offset recorded with the commit and is only
+    /// used by `Timezone::Author`.
+    #[must_use]
+    pub fn wall_clock(self, timestamp: DateTime<Utc>, offset_minutes: i32) -> NaiveDateTime {
+        match self {
+            Self::Local => timestamp.with_timezone(&chrono::Local).naive_local(),
+            Self::Utc => timestamp.naive_utc(),
+            Self::Named(tz) => timestamp.with_timezone(&tz).naive_local(),
+            Self::Author => FixedOffset::east_opt(offset_minutes * 60).map_or_else(
+                || timestamp.naive_utc(),
+                |offset| timestamp.with_timezone(&offset).naive_local(),
+            ),
+        }
+    }
+
+    /// Current date in this timezone (`Author` uses the local date)
+    #[must_use]
+    pub fn today(self) -> NaiveDate {
+        match self {
+            Self::Utc => Utc::now().date_naive(),
+            Self::Named(tz) => Utc::now().with_timezone(&tz).date_naive(),
+            Self::Local | Self::Author => chrono::Local::now().date_naive(),
+        }
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "utc" => Ok(Self::Utc),
+            "author" => Ok(Self::Author),
+            _ => s
+                .parse::<chrono_tz::Tz>()
+                .map(Self::Named)
+                .map_err(|_| Error::InvalidTimezone {
+                    input: s.to_string(),
+                }),
+        }
+    }
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => write!(f, "local"),
+            Self::Utc => write!(f, "utc"),
+            Self::Named(tz) => write!(f, "{}", tz.name()),
+            Self::Author => write!(f, "author"),
+        }
+    }
+}
+
+// Serialized as its name, the same form accepted by `--tz`
+impl serde::Serialize for Timezone {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Timezone {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Which commit date to analyze
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateField {
+    /// When the change was originally written
+    Author,
+    /// When the commit was last created or rewritten (e.g. by a rebase)
+    #[default]
+    Committer,
+}
+
+impl fmt::Display for DateField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Author => write!(f, "author"),
+            Self::Committer => write!(f, "committer"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn timestamp() -> DateTime<Utc> {
+        // 23:30 in Tokyo, still the previous day in UTC
+        Utc.with_ymd_and_hms(2026, 3, 1, 14, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_timezone_from_str() {
+        assert_eq!("local".parse::<Timezone>().unwrap(), Timezone::Local);
+        assert_eq!("UTC".parse::<Timezone>().unwrap(), Timezone::Utc);
+        assert_eq!("author".parse::<Timezone>().unwrap(), Timezone::Author);
+        assert_eq!(
+            "Asia/Tokyo".parse::<Timezone>().unwrap(),
+            Timezone::Named(chrono_tz::Asia::Tokyo)
+        );
+        assert!(matches!(
+            "Mars/Olympus".parse::<Timezone>(),
+            Err(Error::InvalidTimezone { .. })
+        ));
+    }
+
+    #[test]
+    fn test_timezone_display_round_trips() {
+        for input in ["local", "utc", "author", "Europe/Berlin"] {
+            let tz: Timezone = input.parse().unwrap();
+            assert_eq!(tz.to_string(), input);
+        }
+    }
+
+    #[test]
+    fn test_timezone_serde() {
+        let tz: Timezone = serde_json::from_str(r#""Asia/Tokyo""#).unwrap();
+        assert_eq!(tz, Timezone::Named(chrono_tz::Asia::Tokyo));
+        assert_eq!(serde_json::to_string(&tz).unwrap(), r#""Asia/Tokyo""#);
+        assert!(serde_json::from_str::<Timezone>(r#""Mars/Olympus""#).is_err());
+    }
+
+    #[test]
+    fn test_wall_clock() {
+        let ts = timestamp();
+
+        assert_eq!(
+            Timezone::Utc.wall_clock(ts, 540).to_string(),
+            "2026-03-01 14:30:00"
+        );
+        assert_eq!(
+            Timezone::Named(chrono_tz::Asia::Tokyo)
+                .wall_clock(ts, 0)
+                .to_string(),
+            "2026-03-01 23:30:00"
+        );
+        // Author mode uses the commit's own offset (+09:00 here, -05:00 below)
+        assert_eq!(
+            Timezone::Author.wall_clock(ts, 540).to_string(),
+            "2026-03-01 23:30:00"
+        );
+        assert_eq!(
+            Timezone::Author.wall_clock(ts, -300).to_string(),
+            "2026-03-01 09:30:00"
+        );
+    }
+}
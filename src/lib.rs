@@ -20,4 +20,7 @@ pub mod output;
 pub mod stats;
 pub mod tui;
 
-pub use error::{Error, Result};
+#[cfg(test)]
+mod test_support;
+
+pub use error::{Error, RepoWarning, Result};
@@ -1,12 +1,11 @@
 //! kodo CLI entry point
 
-use clap::Parser;
 use kodo::cli::{Args, execute};
 use std::error::Error;
 use std::process::ExitCode;
 
 fn main() -> ExitCode {
-    let args = Args::parse();
+    let args = Args::parse_layered();
 
     if let Err(e) = execute(args) {
         eprintln!("error: {e}");
@@ -1,8 +1,8 @@
 //! CSV output formatter
 
 use crate::error::Result;
-use crate::output::Formatter;
-use crate::stats::AnalysisResult;
+use crate::output::{Field, Formatter, MetricRow};
+use crate::stats::{AnalysisResult, AuthorStats, PeriodStats};
 use std::fmt::Write;
 
 /// CSV output formatter
@@ -38,42 +38,101 @@ impl Default for CsvFormatter {
 impl Formatter for CsvFormatter {
     fn format(&self, result: &AnalysisResult) -> Result<String> {
         let mut output = String::new();
+        let metrics = &result.metrics;
 
         // Add headers if enabled
         if self.include_headers {
-            output.push_str("date,commits,additions,deletions,net_lines,files_changed\n");
+            let _ = writeln!(
+                output,
+                "{}",
+                header_row(&["date"], &MetricRow::fields::<PeriodStats>(metrics))
+            );
         }
 
         // Add data rows
         for stat in &result.stats {
-            let _ = writeln!(
-                output,
-                "{},{},{},{},{},{}",
-                stat.date,
-                stat.commits,
-                stat.additions,
-                stat.deletions,
-                stat.net_lines,
-                stat.files_changed
-            );
+            let labels = [stat.date.to_string()];
+            let _ = writeln!(output, "{}", row(&labels, &MetricRow::new(stat, metrics)));
         }
 
         // Add total row
-        let total = &result.total;
-        let _ = writeln!(
-            output,
-            "TOTAL,{},{},{},{},{}",
-            total.commits, total.additions, total.deletions, total.net_lines, total.files_changed
-        );
+        let total = MetricRow::new(&result.total, metrics);
+        let _ = writeln!(output, "{}", row(&["TOTAL".to_string()], &total));
+
+        // Add per-repository section with a leading repository column
+        if !result.repositories.is_empty() {
+            output.push('\n');
+            if self.include_headers {
+                let fields = MetricRow::fields::<PeriodStats>(metrics);
+                let _ = writeln!(output, "{}", header_row(&["repository", "date"], &fields));
+            }
+            for repo in &result.repositories {
+                let name = escape_field(&repo.name);
+                let rows = repo
+                    .stats
+                    .iter()
+                    .map(|stat| (stat.date.to_string(), MetricRow::new(stat, metrics)))
+                    .chain(std::iter::once((
+                        "TOTAL".to_string(),
+                        MetricRow::new(&repo.total, metrics),
+                    )));
+                for (date, values) in rows {
+                    let _ = writeln!(output, "{}", row(&[name.clone(), date], &values));
+                }
+            }
+        }
+
+        // Add per-author section, separated by a blank line
+        if !result.authors.is_empty() {
+            output.push('\n');
+            if self.include_headers {
+                let fields = MetricRow::fields::<AuthorStats>(metrics);
+                let _ = writeln!(output, "{}", header_row(&["author", "email"], &fields));
+            }
+            for author in &result.authors {
+                let labels = [escape_field(&author.name), escape_field(&author.email)];
+                let _ = writeln!(output, "{}", row(&labels, &MetricRow::new(author, metrics)));
+            }
+        }
 
         Ok(output)
     }
 }
 
+/// Header line of label columns followed by metric fields
+fn header_row(labels: &[&str], fields: &[Field]) -> String {
+    labels
+        .iter()
+        .copied()
+        .chain(fields.iter().map(|field| field.name()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Data line of (escaped) label columns followed by metric values
+fn row(labels: &[String], values: &MetricRow) -> String {
+    labels
+        .iter()
+        .cloned()
+        .chain(values.values().map(|value| value.to_string()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quote a field if it contains characters that are special in CSV
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::stats::{PeriodStats, TotalStats};
+    use crate::stats::MetricGroup;
+    use crate::stats::{RepositoryStats, TotalStats};
     use chrono::NaiveDate;
 
     fn make_result() -> AnalysisResult {
@@ -89,6 +148,8 @@ mod tests {
                 deletions: 20,
                 net_lines: 80,
                 files_changed: 10,
+                binary_files: 1,
+                binary_bytes: 2048,
             },
             PeriodStats {
                 label: "2024-01-02".to_string(),
@@ -98,6 +159,7 @@ mod tests {
                 deletions: 10,
                 net_lines: 40,
                 files_changed: 5,
+                ..Default::default()
             },
         ];
 
@@ -113,7 +175,14 @@ mod tests {
                 deletions: 30,
                 net_lines: 120,
                 files_changed: 15,
+                binary_files: 1,
+                binary_bytes: 2048,
             },
+            authors: Vec::new(),
+            repositories: Vec::new(),
+            out_of_range_commits: 0,
+            warnings: Vec::new(),
+            metrics: MetricGroup::ALL.to_vec(),
         }
     }
 
@@ -124,10 +193,12 @@ mod tests {
 
         let csv = formatter.format(&result).unwrap();
 
-        assert!(csv.starts_with("date,commits,additions,deletions,net_lines,files_changed\n"));
-        assert!(csv.contains("2024-01-01,5,100,20,80,10\n"));
-        assert!(csv.contains("2024-01-02,3,50,10,40,5\n"));
-        assert!(csv.contains("TOTAL,8,150,30,120,15\n"));
+        assert!(csv.starts_with(
+            "date,commits,additions,deletions,net_lines,files_changed,binary_files,binary_bytes\n"
+        ));
+        assert!(csv.contains("2024-01-01,5,100,20,80,10,1,2048\n"));
+        assert!(csv.contains("2024-01-02,3,50,10,40,5,0,0\n"));
+        assert!(csv.contains("TOTAL,8,150,30,120,15,1,2048\n"));
     }
 
     #[test]
@@ -138,7 +209,7 @@ mod tests {
         let csv = formatter.format(&result).unwrap();
 
         assert!(!csv.starts_with("date,"));
-        assert!(csv.starts_with("2024-01-01,5,100,20,80,10\n"));
+        assert!(csv.starts_with("2024-01-01,5,100,20,80,10,1,2048\n"));
     }
 
     #[test]
@@ -169,6 +240,7 @@ mod tests {
                 deletions: 50,
                 net_lines: -40,
                 files_changed: 1,
+                ..Default::default()
             }],
             total: TotalStats {
                 commits: 1,
@@ -176,7 +248,13 @@ mod tests {
                 deletions: 50,
                 net_lines: -40,
                 files_changed: 1,
+                ..Default::default()
             },
+            authors: Vec::new(),
+            repositories: Vec::new(),
+            out_of_range_commits: 0,
+            warnings: Vec::new(),
+            metrics: MetricGroup::ALL.to_vec(),
         };
 
         let formatter = CsvFormatter::new();
@@ -184,4 +262,58 @@ mod tests {
 
         assert!(csv.contains("-40"));
     }
+
+    #[test]
+    fn test_csv_author_section() {
+        let mut result = make_result();
+        result.authors = vec![AuthorStats {
+            name: "Smith, Alice".to_string(),
+            email: "alice@example.com".to_string(),
+            commits: 8,
+            additions: 150,
+            deletions: 30,
+            net_lines: 120,
+            files_changed: 15,
+        }];
+
+        let csv = CsvFormatter::new().format(&result).unwrap();
+
+        assert!(csv.contains(
+            "TOTAL,8,150,30,120,15,1,2048\n\nauthor,email,commits,additions,deletions,net_lines,files_changed\n"
+        ));
+        assert!(csv.contains("\"Smith, Alice\",alice@example.com,8,150,30,120,15\n"));
+    }
+
+    #[test]
+    fn test_csv_repository_section() {
+        let mut result = make_result();
+        result.repositories = vec![
+            RepositoryStats::new("api".to_string(), result.stats[..1].to_vec()),
+            RepositoryStats::new("my, web".to_string(), result.stats[1..].to_vec()),
+        ];
+        result.metrics = vec![MetricGroup::Commits];
+
+        let csv = CsvFormatter::new().format(&result).unwrap();
+
+        assert!(csv.contains("TOTAL,8\n\nrepository,date,commits\n"));
+        assert!(csv.contains("api,2024-01-01,5\napi,TOTAL,5\n"));
+        assert!(csv.contains("\"my, web\",2024-01-02,3\n\"my, web\",TOTAL,3\n"));
+    }
+
+    #[test]
+    fn test_csv_omits_unselected_metrics() {
+        let mut result = make_result();
+        result.metrics = vec![MetricGroup::Commits];
+        result.authors = vec![AuthorStats::new(
+            "Alice".to_string(),
+            "alice@example.com".to_string(),
+        )];
+
+        let csv = CsvFormatter::new().format(&result).unwrap();
+        assert!(csv.starts_with("date,commits\n"));
+        assert!(csv.contains("2024-01-01,5\n"));
+        assert!(csv.contains("TOTAL,8\n"));
+        assert!(csv.contains("author,email,commits\n"));
+        assert!(csv.contains("Alice,alice@example.com,0\n"));
+    }
 }
@@ -1,7 +1,35 @@
-//! Output formatter trait
+//! Output formats and the formatter trait
 
 use crate::error::Result;
 use crate::stats::AnalysisResult;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Output format options
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Terminal UI with charts
+    Tui,
+    /// Table output
+    #[default]
+    Table,
+    /// JSON output
+    Json,
+    /// CSV output
+    Csv,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tui => write!(f, "tui"),
+            Self::Table => write!(f, "table"),
+            Self::Json => write!(f, "json"),
+            Self::Csv => write!(f, "csv"),
+        }
+    }
+}
 
 /// Trait for output formatters
 pub trait Formatter {
@@ -12,3 +40,16 @@ pub trait Formatter {
     /// Returns an error if formatting fails
     fn format(&self, result: &AnalysisResult) -> Result<String>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_format_display() {
+        assert_eq!(OutputFormat::Tui.to_string(), "tui");
+        assert_eq!(OutputFormat::Table.to_string(), "table");
+        assert_eq!(OutputFormat::Json.to_string(), "json");
+        assert_eq!(OutputFormat::Csv.to_string(), "csv");
+    }
+}
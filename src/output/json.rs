@@ -1,8 +1,10 @@
 //! JSON output formatter
 
-use crate::error::Result;
-use crate::output::Formatter;
-use crate::stats::AnalysisResult;
+use crate::error::{RepoWarning, Result};
+use crate::output::{Formatter, MetricRow};
+use crate::stats::{AnalysisResult, MetricGroup, PeriodStats};
+use chrono::NaiveDate;
+use serde::Serialize;
 
 /// JSON output formatter
 pub struct JsonFormatter {
@@ -32,19 +34,112 @@ impl Default for JsonFormatter {
 
 impl Formatter for JsonFormatter {
     fn format(&self, result: &AnalysisResult) -> Result<String> {
+        let output = JsonResult::new(result);
+
         let json = if self.pretty {
-            serde_json::to_string_pretty(result)?
+            serde_json::to_string_pretty(&output)?
         } else {
-            serde_json::to_string(result)?
+            serde_json::to_string(&output)?
         };
         Ok(json)
     }
 }
 
+/// JSON view of an analysis result, with only the selected metrics in each row
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    repository: &'a str,
+    period: &'a str,
+    from: NaiveDate,
+    to: NaiveDate,
+    stats: Vec<JsonPeriod<'a>>,
+    total: MetricRow,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<JsonAuthor<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    repositories: Vec<JsonRepository<'a>>,
+    out_of_range_commits: usize,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    warnings: &'a [RepoWarning],
+}
+
+impl<'a> JsonResult<'a> {
+    fn new(result: &'a AnalysisResult) -> Self {
+        let metrics = &result.metrics;
+        Self {
+            repository: &result.repository,
+            period: &result.period,
+            from: result.from,
+            to: result.to,
+            stats: JsonPeriod::rows(&result.stats, metrics),
+            total: MetricRow::new(&result.total, metrics),
+            authors: result
+                .authors
+                .iter()
+                .map(|author| JsonAuthor {
+                    name: &author.name,
+                    email: &author.email,
+                    metrics: MetricRow::new(author, metrics),
+                })
+                .collect(),
+            repositories: result
+                .repositories
+                .iter()
+                .map(|repo| JsonRepository {
+                    name: &repo.name,
+                    stats: JsonPeriod::rows(&repo.stats, metrics),
+                    total: MetricRow::new(&repo.total, metrics),
+                })
+                .collect(),
+            out_of_range_commits: result.out_of_range_commits,
+            warnings: &result.warnings,
+        }
+    }
+}
+
+/// A period row: its label and start date, then the selected metrics
+#[derive(Serialize)]
+struct JsonPeriod<'a> {
+    label: &'a str,
+    date: NaiveDate,
+    #[serde(flatten)]
+    metrics: MetricRow,
+}
+
+impl<'a> JsonPeriod<'a> {
+    fn rows(stats: &'a [PeriodStats], metrics: &[MetricGroup]) -> Vec<Self> {
+        stats
+            .iter()
+            .map(|stat| Self {
+                label: &stat.label,
+                date: stat.date,
+                metrics: MetricRow::new(stat, metrics),
+            })
+            .collect()
+    }
+}
+
+/// An author row: name and email, then the selected metrics
+#[derive(Serialize)]
+struct JsonAuthor<'a> {
+    name: &'a str,
+    email: &'a str,
+    #[serde(flatten)]
+    metrics: MetricRow,
+}
+
+/// Periods and total of one repository
+#[derive(Serialize)]
+struct JsonRepository<'a> {
+    name: &'a str,
+    stats: Vec<JsonPeriod<'a>>,
+    total: MetricRow,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::stats::{PeriodStats, TotalStats};
+    use crate::stats::{AuthorStats, RepositoryStats, TotalStats};
     use chrono::NaiveDate;
 
     fn make_result() -> AnalysisResult {
@@ -60,6 +155,8 @@ mod tests {
                 deletions: 20,
                 net_lines: 80,
                 files_changed: 10,
+                binary_files: 1,
+                binary_bytes: 2048,
             },
             PeriodStats {
                 label: "2024-01-02".to_string(),
@@ -69,6 +166,7 @@ mod tests {
                 deletions: 10,
                 net_lines: 40,
                 files_changed: 5,
+                ..Default::default()
             },
         ];
 
@@ -84,7 +182,14 @@ mod tests {
                 deletions: 30,
                 net_lines: 120,
                 files_changed: 15,
+                binary_files: 1,
+                binary_bytes: 2048,
             },
+            authors: Vec::new(),
+            repositories: Vec::new(),
+            out_of_range_commits: 0,
+            warnings: Vec::new(),
+            metrics: MetricGroup::ALL.to_vec(),
         }
     }
 
@@ -125,6 +230,8 @@ mod tests {
         assert_eq!(parsed["repository"], "test-repo");
         assert_eq!(parsed["period"], "daily");
         assert_eq!(parsed["stats"].as_array().unwrap().len(), 2);
+        assert_eq!(parsed["stats"][0]["binary_files"], 1);
+        assert_eq!(parsed["total"]["binary_bytes"], 2048);
     }
 
     #[test]
@@ -138,4 +245,71 @@ mod tests {
         assert!(json.contains("\"from\": \"2024-01-01\""));
         assert!(json.contains("\"to\": \"2024-01-07\""));
     }
+
+    #[test]
+    fn test_json_includes_authors_when_present() {
+        let formatter = JsonFormatter::new();
+        let mut result = make_result();
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&formatter.format(&result).unwrap()).unwrap();
+        assert!(parsed.get("authors").is_none());
+
+        result.authors = vec![AuthorStats::new(
+            "Alice".to_string(),
+            "alice@example.com".to_string(),
+        )];
+        let parsed: serde_json::Value =
+            serde_json::from_str(&formatter.format(&result).unwrap()).unwrap();
+        assert_eq!(parsed["authors"][0]["email"], "alice@example.com");
+    }
+
+    #[test]
+    fn test_json_nests_repositories() {
+        let formatter = JsonFormatter::new();
+        let mut result = make_result();
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&formatter.format(&result).unwrap()).unwrap();
+        assert!(parsed.get("repositories").is_none());
+
+        result.repositories = vec![
+            RepositoryStats::new("api".to_string(), result.stats[..1].to_vec()),
+            RepositoryStats::new("web".to_string(), result.stats[1..].to_vec()),
+        ];
+        result.metrics = vec![MetricGroup::Commits];
+        let parsed: serde_json::Value =
+            serde_json::from_str(&formatter.format(&result).unwrap()).unwrap();
+        let repos = parsed["repositories"].as_array().unwrap();
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0]["name"], "api");
+        assert_eq!(repos[0]["stats"][0]["commits"], 5);
+        assert_eq!(repos[1]["total"]["commits"], 3);
+        assert!(repos[0]["stats"][0].get("additions").is_none());
+        assert!(repos[1]["total"].get("additions").is_none());
+    }
+
+    #[test]
+    fn test_json_omits_unselected_metrics() {
+        let mut result = make_result();
+        result.metrics = vec![MetricGroup::Commits, MetricGroup::Files];
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&JsonFormatter::new().format(&result).unwrap()).unwrap();
+        for row in [&parsed["stats"][0], &parsed["total"]] {
+            assert!(row.get("commits").is_some());
+            assert!(row.get("files_changed").is_some());
+            assert!(row.get("additions").is_none());
+            assert!(row.get("net_lines").is_none());
+            assert!(row.get("binary_bytes").is_none());
+        }
+        assert_eq!(parsed["stats"][0]["label"], "2024-01-01");
+    }
+
+    #[test]
+    fn test_json_keeps_field_order() {
+        let json = JsonFormatter::compact().format(&make_result()).unwrap();
+        assert!(json.starts_with(r#"{"repository":"#));
+        assert!(json.contains(r#"{"label":"2024-01-01","date":"2024-01-01","commits":5"#));
+    }
 }
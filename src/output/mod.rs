@@ -6,6 +6,217 @@ pub mod json;
 pub mod table;
 
 pub use csv::CsvFormatter;
-pub use format::Formatter;
+pub use format::{Formatter, OutputFormat};
 pub use json::JsonFormatter;
 pub use table::TableFormatter;
+
+use crate::stats::{AuthorStats, MetricGroup, PeriodStats, TotalStats};
+use serde::{Serialize, Serializer, ser::SerializeMap};
+
+/// A metric field of the output rows, in output column order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Commits,
+    Additions,
+    Deletions,
+    NetLines,
+    FilesChanged,
+    BinaryFiles,
+    BinaryBytes,
+}
+
+impl Field {
+    const ALL: [Self; 7] = [
+        Self::Commits,
+        Self::Additions,
+        Self::Deletions,
+        Self::NetLines,
+        Self::FilesChanged,
+        Self::BinaryFiles,
+        Self::BinaryBytes,
+    ];
+
+    /// Metric group that selects this field
+    const fn metric(self) -> MetricGroup {
+        match self {
+            Self::Commits => MetricGroup::Commits,
+            Self::Additions | Self::Deletions | Self::NetLines => MetricGroup::Lines,
+            Self::FilesChanged => MetricGroup::Files,
+            Self::BinaryFiles | Self::BinaryBytes => MetricGroup::Binary,
+        }
+    }
+
+    /// Field name in JSON and CSV output
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Commits => "commits",
+            Self::Additions => "additions",
+            Self::Deletions => "deletions",
+            Self::NetLines => "net_lines",
+            Self::FilesChanged => "files_changed",
+            Self::BinaryFiles => "binary_files",
+            Self::BinaryBytes => "binary_bytes",
+        }
+    }
+
+    /// Column header in table output
+    const fn header(self) -> &'static str {
+        match self {
+            Self::Commits => "Commits",
+            Self::Additions => "+Lines",
+            Self::Deletions => "-Lines",
+            Self::NetLines => "Net",
+            Self::FilesChanged => "Files",
+            Self::BinaryFiles => "Bin Files",
+            Self::BinaryBytes => "Bin Bytes",
+        }
+    }
+}
+
+/// A metric value of an output row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+enum MetricValue {
+    /// A count or size
+    Count(u64),
+    /// A net change that may be negative
+    Net(i64),
+}
+
+impl std::fmt::Display for MetricValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Count(value) => write!(f, "{value}"),
+            Self::Net(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Stats rows that carry metric fields
+trait MetricFields: Default {
+    /// Value of a field, or `None` if this kind of row has no such field
+    fn field(&self, field: Field) -> Option<MetricValue>;
+}
+
+impl MetricFields for PeriodStats {
+    fn field(&self, field: Field) -> Option<MetricValue> {
+        Some(match field {
+            Field::Commits => MetricValue::Count(u64::from(self.commits)),
+            Field::Additions => MetricValue::Count(self.additions),
+            Field::Deletions => MetricValue::Count(self.deletions),
+            Field::NetLines => MetricValue::Net(self.net_lines),
+            Field::FilesChanged => MetricValue::Count(u64::from(self.files_changed)),
+            Field::BinaryFiles => MetricValue::Count(u64::from(self.binary_files)),
+            Field::BinaryBytes => MetricValue::Count(self.binary_bytes),
+        })
+    }
+}
+
+impl MetricFields for TotalStats {
+    fn field(&self, field: Field) -> Option<MetricValue> {
+        Some(match field {
+            Field::Commits => MetricValue::Count(u64::from(self.commits)),
+            Field::Additions => MetricValue::Count(self.additions),
+            Field::Deletions => MetricValue::Count(self.deletions),
+            Field::NetLines => MetricValue::Net(self.net_lines),
+            Field::FilesChanged => MetricValue::Count(u64::from(self.files_changed)),
+            Field::BinaryFiles => MetricValue::Count(u64::from(self.binary_files)),
+            Field::BinaryBytes => MetricValue::Count(self.binary_bytes),
+        })
+    }
+}
+
+impl MetricFields for AuthorStats {
+    fn field(&self, field: Field) -> Option<MetricValue> {
+        match field {
+            Field::Commits => Some(MetricValue::Count(u64::from(self.commits))),
+            Field::Additions => Some(MetricValue::Count(self.additions)),
+            Field::Deletions => Some(MetricValue::Count(self.deletions)),
+            Field::NetLines => Some(MetricValue::Net(self.net_lines)),
+            Field::FilesChanged => Some(MetricValue::Count(u64::from(self.files_changed))),
+            Field::BinaryFiles | Field::BinaryBytes => None,
+        }
+    }
+}
+
+/// The selected metric fields of one output row, in column order
+///
+/// Serializes as a map of field names to values, so it can be flattened into a
+/// JSON row next to its labels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MetricRow(Vec<(Field, MetricValue)>);
+
+impl MetricRow {
+    /// Fields of `row` that belong to one of `metrics`
+    fn new<T: MetricFields>(row: &T, metrics: &[MetricGroup]) -> Self {
+        Self(
+            Field::ALL
+                .into_iter()
+                .filter(|field| metrics.contains(&field.metric()))
+                .filter_map(|field| Some((field, row.field(field)?)))
+                .collect(),
+        )
+    }
+
+    /// Fields of the rows of type `T` that belong to one of `metrics`, for headers
+    fn fields<T: MetricFields>(metrics: &[MetricGroup]) -> Vec<Field> {
+        Self::new(&T::default(), metrics)
+            .0
+            .into_iter()
+            .map(|(field, _)| field)
+            .collect()
+    }
+
+    /// Values of the fields, in column order
+    fn values(&self) -> impl Iterator<Item = MetricValue> + '_ {
+        self.0.iter().map(|(_, value)| *value)
+    }
+}
+
+impl Serialize for MetricRow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (field, value) in &self.0 {
+            map.serialize_entry(field.name(), value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_row_follows_selected_metrics() {
+        let total = TotalStats {
+            commits: 3,
+            net_lines: -4,
+            binary_bytes: 10,
+            ..TotalStats::default()
+        };
+
+        let row = MetricRow::new(&total, &[MetricGroup::Binary, MetricGroup::Lines]);
+        let names: Vec<_> = row.0.iter().map(|(field, _)| field.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "additions",
+                "deletions",
+                "net_lines",
+                "binary_files",
+                "binary_bytes"
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&row).unwrap(),
+            r#"{"additions":0,"deletions":0,"net_lines":-4,"binary_files":0,"binary_bytes":10}"#
+        );
+
+        // Author rows have no binary fields
+        assert_eq!(
+            MetricRow::fields::<AuthorStats>(&MetricGroup::ALL),
+            Field::ALL[..5]
+        );
+    }
+}
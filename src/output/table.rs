@@ -1,8 +1,8 @@
 //! Table output formatter
 
 use crate::error::Result;
-use crate::output::Formatter;
-use crate::stats::AnalysisResult;
+use crate::output::{Field, Formatter, MetricRow, MetricValue};
+use crate::stats::{AnalysisResult, AuthorStats, PeriodStats};
 use comfy_table::{Table, presets::UTF8_FULL};
 
 /// Table output formatter
@@ -36,6 +36,32 @@ fn format_with_commas_i64(value: i64) -> String {
     }
 }
 
+/// Table cell of a metric value, with thousands separators
+fn format_value(value: MetricValue) -> String {
+    match value {
+        MetricValue::Count(value) => format_with_commas_u64(value),
+        MetricValue::Net(value) => format_with_commas_i64(value),
+    }
+}
+
+/// Header of label columns followed by metric columns
+fn header_row(labels: &[&str], fields: &[Field]) -> Vec<String> {
+    labels
+        .iter()
+        .copied()
+        .chain(fields.iter().map(|field| field.header()))
+        .map(String::from)
+        .collect()
+}
+
+/// Row of label cells followed by metric cells
+fn row(labels: Vec<String>, values: &MetricRow) -> Vec<String> {
+    labels
+        .into_iter()
+        .chain(values.values().map(format_value))
+        .collect()
+}
+
 impl Default for TableFormatter {
     fn default() -> Self {
         Self::new()
@@ -44,40 +70,84 @@ impl Default for TableFormatter {
 
 impl Formatter for TableFormatter {
     fn format(&self, result: &AnalysisResult) -> Result<String> {
+        let metrics = &result.metrics;
+        let period_fields = MetricRow::fields::<PeriodStats>(metrics);
         let mut table = Table::new();
         table
             .load_preset(UTF8_FULL)
-            .set_header(["Period", "Commits", "+Lines", "-Lines", "Net", "Files"]);
+            .set_header(header_row(&["Period"], &period_fields));
 
         for stat in &result.stats {
-            table.add_row([
-                stat.label.clone(),
-                format_with_commas_u64(u64::from(stat.commits)),
-                format_with_commas_u64(stat.additions),
-                format_with_commas_u64(stat.deletions),
-                format_with_commas_i64(stat.net_lines),
-                format_with_commas_u64(u64::from(stat.files_changed)),
-            ]);
+            table.add_row(row(
+                vec![stat.label.clone()],
+                &MetricRow::new(stat, metrics),
+            ));
         }
 
-        let total = &result.total;
-        table.add_row([
-            "TOTAL".to_string(),
-            format_with_commas_u64(u64::from(total.commits)),
-            format_with_commas_u64(total.additions),
-            format_with_commas_u64(total.deletions),
-            format_with_commas_i64(total.net_lines),
-            format_with_commas_u64(u64::from(total.files_changed)),
-        ]);
-
-        Ok(table.to_string())
+        table.add_row(row(
+            vec!["TOTAL".to_string()],
+            &MetricRow::new(&result.total, metrics),
+        ));
+
+        let mut output = table.to_string();
+
+        // Per-repository rows, grouped under each repository name
+        if !result.repositories.is_empty() {
+            let mut repositories = Table::new();
+            repositories
+                .load_preset(UTF8_FULL)
+                .set_header(header_row(&["Repository", "Period"], &period_fields));
+
+            for repo in &result.repositories {
+                let rows = repo
+                    .stats
+                    .iter()
+                    .map(|stat| (stat.label.clone(), MetricRow::new(stat, metrics)))
+                    .chain(std::iter::once((
+                        "TOTAL".to_string(),
+                        MetricRow::new(&repo.total, metrics),
+                    )));
+                for (i, (label, values)) in rows.enumerate() {
+                    let name = if i == 0 {
+                        repo.name.clone()
+                    } else {
+                        String::new()
+                    };
+                    repositories.add_row(row(vec![name, label], &values));
+                }
+            }
+
+            output.push('\n');
+            output.push_str(&repositories.to_string());
+        }
+
+        if !result.authors.is_empty() {
+            let mut authors = Table::new();
+            authors.load_preset(UTF8_FULL).set_header(header_row(
+                &["Author", "Email"],
+                &MetricRow::fields::<AuthorStats>(metrics),
+            ));
+
+            for author in &result.authors {
+                authors.add_row(row(
+                    vec![author.name.clone(), author.email.clone()],
+                    &MetricRow::new(author, metrics),
+                ));
+            }
+
+            output.push('\n');
+            output.push_str(&authors.to_string());
+        }
+
+        Ok(output)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::stats::{AnalysisResult, PeriodStats};
+    use crate::stats::MetricGroup;
+    use crate::stats::{AnalysisResult, AuthorStats, PeriodStats, RepositoryStats};
     use chrono::NaiveDate;
 
     fn make_result() -> AnalysisResult {
@@ -91,6 +161,7 @@ mod tests {
             deletions: 5,
             net_lines: 15,
             files_changed: 3,
+            ..Default::default()
         }];
 
         AnalysisResult::new(
@@ -128,6 +199,8 @@ mod tests {
             deletions: 12_345,
             net_lines: -1_234_567,
             files_changed: 9_999,
+            binary_files: 2,
+            binary_bytes: 3_500_000,
         }];
 
         let result = AnalysisResult::new(
@@ -145,5 +218,70 @@ mod tests {
         assert!(table.contains("12,345"));
         assert!(table.contains("-1,234,567"));
         assert!(table.contains("9,999"));
+        assert!(table.contains("Bin Bytes"));
+        assert!(table.contains("3,500,000"));
+    }
+
+    #[test]
+    fn test_table_formatter_author_section() {
+        let mut result = make_result();
+        assert!(
+            !TableFormatter::new()
+                .format(&result)
+                .unwrap()
+                .contains("Author")
+        );
+
+        result.authors = vec![AuthorStats {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+            commits: 2,
+            additions: 1_500,
+            deletions: 5,
+            net_lines: 1_495,
+            files_changed: 3,
+        }];
+        let table = TableFormatter::new().format(&result).unwrap();
+
+        assert!(table.contains("Author"));
+        assert!(table.contains("alice@example.com"));
+        assert!(table.contains("1,495"));
+    }
+
+    #[test]
+    fn test_table_formatter_repository_section() {
+        let mut result = make_result();
+        assert!(
+            !TableFormatter::new()
+                .format(&result)
+                .unwrap()
+                .contains("Repository")
+        );
+
+        let mut api = result.stats.clone();
+        api[0].commits = 1_234;
+        result.repositories = vec![
+            RepositoryStats::new("api".to_string(), api),
+            RepositoryStats::new("web".to_string(), result.stats.clone()),
+        ];
+        let table = TableFormatter::new().format(&result).unwrap();
+
+        assert!(table.contains("Repository"));
+        assert!(table.contains("api"));
+        assert!(table.contains("web"));
+        assert!(table.contains("1,234"));
+        assert_eq!(table.matches("TOTAL").count(), 3);
+    }
+
+    #[test]
+    fn test_table_omits_unselected_metrics() {
+        let mut result = make_result();
+        result.metrics = vec![MetricGroup::Commits];
+        let table = TableFormatter::new().format(&result).unwrap();
+
+        assert!(table.contains("Commits"));
+        assert!(!table.contains("+Lines"));
+        assert!(!table.contains("Files"));
+        assert!(!table.contains("Bin Bytes"));
     }
 }
@@ -39,6 +39,8 @@ pub fn running_totals(stats: &[PeriodStats]) -> Vec<PeriodStats> {
         running.additions += stat.additions;
         running.deletions += stat.deletions;
         running.files_changed += stat.files_changed;
+        running.binary_files += stat.binary_files;
+        running.binary_bytes += stat.binary_bytes;
         running.update_net_lines();
 
         let mut period = running.clone();
@@ -2,60 +2,74 @@
 
 #![allow(clippy::cast_possible_truncation)]
 
-use crate::cli::args::Period;
-use crate::git::CommitInfo;
-use crate::stats::types::{ActivityStats, AnalysisResult, DateRange, PeriodStats};
-use chrono::{Datelike, Local, NaiveDate, Timelike};
+use crate::git::{CommitInfo, DiffStats, Timezone};
+use crate::stats::types::{
+    ActivityStats, AnalysisResult, AuthorStats, DateRange, Grouping, PeriodStats, RepositoryStats,
+};
+use crate::stats::{Period, WeekLabel, WeekStart};
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc};
 use std::collections::HashMap;
 
 /// Collect statistics from a list of commits
 ///
-/// Groups commits by the specified period and calculates aggregate statistics.
-/// Days with no commits are included with zero values.
+/// Groups commits by the specified period (a [`Period`] or fixed-length
+/// [`Grouping::Buckets`]) and calculates aggregate statistics. Commits are
+/// assigned to days in the given timezone. Days with no commits are included
+/// with zero values.
 #[must_use]
 pub fn collect_stats(
     repo_name: &str,
-    commits: Vec<CommitInfo>,
+    commits: &[CommitInfo],
     range: DateRange,
-    period: Period,
-    extensions: Option<&[String]>,
+    grouping: impl Into<Grouping>,
+    tz: Timezone,
 ) -> AnalysisResult {
+    let grouping = grouping.into();
+    AnalysisResult::new(
+        repo_name.to_string(),
+        grouping.to_string(),
+        range.from,
+        range.to,
+        period_stats(commits, range, grouping, tz),
+    )
+}
+
+/// Collect statistics for one repository of a multi-repository analysis
+///
+/// Uses the same date range and periods as [`collect_stats`], so the series of
+/// every repository lines up with the combined one.
+#[must_use]
+pub fn collect_repository_stats(
+    repo_name: &str,
+    commits: &[CommitInfo],
+    range: DateRange,
+    grouping: impl Into<Grouping>,
+    tz: Timezone,
+) -> RepositoryStats {
+    RepositoryStats::new(
+        repo_name.to_string(),
+        period_stats(commits, range, grouping.into(), tz),
+    )
+}
+
+/// Group commits into periods covering the whole range
+fn period_stats(
+    commits: &[CommitInfo],
+    range: DateRange,
+    grouping: Grouping,
+    tz: Timezone,
+) -> Vec<PeriodStats> {
     // Group commits by date
     let mut daily_stats: HashMap<NaiveDate, PeriodStats> = HashMap::new();
 
     for commit in commits {
-        let date = commit.date();
-
-        // Filter by extensions if specified
-        let (additions, deletions, files_changed) = if let Some(exts) = extensions {
-            let filtered: Vec<_> = commit
-                .diff
-                .files
-                .iter()
-                .filter(|f| f.matches_extensions(exts))
-                .collect();
-
-            (
-                filtered.iter().map(|f| f.additions).sum(),
-                filtered.iter().map(|f| f.deletions).sum(),
-                filtered.len() as u32,
-            )
-        } else {
-            (
-                commit.diff.additions,
-                commit.diff.deletions,
-                commit.diff.files_changed,
-            )
-        };
+        let date = commit.date(tz);
+        let totals = diff_totals(commit);
 
-        let entry = daily_stats
+        daily_stats
             .entry(date)
-            .or_insert_with(|| PeriodStats::new(date));
-        entry.commits += 1;
-        entry.additions += additions;
-        entry.deletions += deletions;
-        entry.files_changed += files_changed;
-        entry.update_net_lines();
+            .or_insert_with(|| PeriodStats::new(date))
+            .add_commit(&totals);
     }
 
     // Fill in missing days with zero stats
@@ -70,90 +84,156 @@ pub fn collect_stats(
     stats.sort_by_key(|s| s.date);
 
     // Apply period aggregation if not daily
-    let stats = match period {
-        Period::Daily => stats,
-        Period::Weekly => aggregate_by_week(stats),
-        Period::Monthly => aggregate_by_month(stats),
-        Period::Yearly => aggregate_by_year(stats),
-    };
-
-    AnalysisResult::new(
-        repo_name.to_string(),
-        period.to_string(),
-        range.from,
-        range.to,
-        stats,
-    )
+    match grouping {
+        Grouping::Calendar {
+            period: Period::Daily,
+            ..
+        } => stats,
+        Grouping::Calendar {
+            period: Period::Weekly,
+            week_start,
+            week_label,
+        } => aggregate(stats, |date| week_period(week_start, week_label, date)),
+        Grouping::Calendar { period, .. } => aggregate(stats, |date| calendar_period(period, date)),
+        Grouping::Buckets { days, start } => {
+            let anchor = start.unwrap_or(range.from);
+            aggregate(stats, |date| bucket_period(days, anchor, date))
+        }
+    }
 }
 
-/// Aggregate daily stats by ISO week
-fn aggregate_by_week(daily_stats: Vec<PeriodStats>) -> Vec<PeriodStats> {
-    let mut weekly: HashMap<(i32, u32), PeriodStats> = HashMap::new();
+/// Collect per-author statistics from a list of commits
+///
+/// Authors are identified by email (case-insensitive). The name shown is the one
+/// from the most recent commit. Results are sorted by commit count, descending.
+#[must_use]
+pub fn collect_author_stats(commits: &[CommitInfo]) -> Vec<AuthorStats> {
+    let mut by_author: HashMap<String, AuthorStats> = HashMap::new();
+    let mut latest: HashMap<String, DateTime<Utc>> = HashMap::new();
 
-    for stat in daily_stats {
-        let week = stat.date.iso_week();
-        let key = (week.year(), week.week());
+    for commit in commits {
+        let key = commit.author_email.to_lowercase();
+        let totals = diff_totals(commit);
 
-        let entry = weekly.entry(key).or_insert_with(|| {
-            PeriodStats::with_label(stat.date, format!("{}-W{:02}", week.year(), week.week()))
+        let entry = by_author.entry(key.clone()).or_insert_with(|| {
+            AuthorStats::new(commit.author_name.clone(), commit.author_email.clone())
         });
-        entry.merge(&stat);
+
+        // Keep the name from the newest commit
+        let newest = latest.entry(key).or_insert(commit.timestamp);
+        if commit.timestamp > *newest {
+            *newest = commit.timestamp;
+            entry.name.clone_from(&commit.author_name);
+        }
+
+        entry.commits += 1;
+        entry.additions += totals.additions;
+        entry.deletions += totals.deletions;
+        entry.files_changed += totals.files_changed;
+        entry.update_net_lines();
     }
 
-    let mut result: Vec<_> = weekly.into_values().collect();
-    result.sort_by_key(|s| s.date);
+    let mut result: Vec<_> = by_author.into_values().collect();
+    result.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
     result
 }
 
-/// Aggregate daily stats by month
-fn aggregate_by_month(daily_stats: Vec<PeriodStats>) -> Vec<PeriodStats> {
-    let mut monthly: HashMap<(i32, u32), PeriodStats> = HashMap::new();
+/// A commit's diff totals, without the per-file entries
+fn diff_totals(commit: &CommitInfo) -> DiffStats {
+    DiffStats {
+        files: Vec::new(),
+        ..commit.diff.clone()
+    }
+}
+
+/// Merge daily stats into longer periods
+///
+/// `period_of` maps a day to the start date and label of the period containing it.
+/// Each period is dated by its start, even when the range begins partway through it.
+fn aggregate(
+    daily_stats: Vec<PeriodStats>,
+    period_of: impl Fn(NaiveDate) -> (NaiveDate, String),
+) -> Vec<PeriodStats> {
+    let mut periods: HashMap<NaiveDate, PeriodStats> = HashMap::new();
 
     for stat in daily_stats {
-        let key = (stat.date.year(), stat.date.month());
+        let (start, label) = period_of(stat.date);
 
-        let entry = monthly.entry(key).or_insert_with(|| {
-            PeriodStats::with_label(
-                stat.date,
-                format!("{}-{:02}", stat.date.year(), stat.date.month()),
-            )
-        });
+        let entry = periods
+            .entry(start)
+            .or_insert_with(|| PeriodStats::with_label(start, label));
         entry.merge(&stat);
     }
 
-    let mut result: Vec<_> = monthly.into_values().collect();
+    let mut result: Vec<_> = periods.into_values().collect();
     result.sort_by_key(|s| s.date);
     result
 }
 
-/// Aggregate daily stats by year
-fn aggregate_by_year(daily_stats: Vec<PeriodStats>) -> Vec<PeriodStats> {
-    let mut yearly: HashMap<i32, PeriodStats> = HashMap::new();
-
-    for stat in daily_stats {
-        let year = stat.date.year();
-
-        let entry = yearly
-            .entry(year)
-            .or_insert_with(|| PeriodStats::with_label(stat.date, year.to_string()));
-        entry.merge(&stat);
+/// Start date and label of the calendar period containing a day
+fn calendar_period(period: Period, date: NaiveDate) -> (NaiveDate, String) {
+    let (year, month) = (date.year(), date.month());
+    let first_of = |month| NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(date);
+
+    match period {
+        Period::Daily => (date, date.format("%Y-%m-%d").to_string()),
+        Period::Weekly => week_period(WeekStart::Monday, WeekLabel::Iso, date),
+        Period::Monthly => (first_of(month), format!("{year}-{month:02}")),
+        Period::Quarterly => {
+            let quarter = (month - 1) / 3 + 1;
+            (first_of(quarter * 3 - 2), format!("{year}-Q{quarter}"))
+        }
+        Period::Yearly => (first_of(1), year.to_string()),
     }
+}
 
-    let mut result: Vec<_> = yearly.into_values().collect();
-    result.sort_by_key(|s| s.date);
-    result
+/// Start date and label of the week containing a day
+///
+/// ISO labels of Sunday-start weeks use the ISO week of the following Monday.
+fn week_period(
+    week_start: WeekStart,
+    week_label: WeekLabel,
+    date: NaiveDate,
+) -> (NaiveDate, String) {
+    let offset = week_start.days_into_week(date.weekday());
+    let start = date - chrono::Duration::days(i64::from(offset));
+    let end = start + chrono::Duration::days(6);
+
+    let label = match week_label {
+        WeekLabel::Iso => {
+            let monday = match week_start {
+                WeekStart::Monday => start,
+                WeekStart::Sunday => start + chrono::Duration::days(1),
+            };
+            let week = monday.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        }
+        WeekLabel::StartDate => start.format("%Y-%m-%d").to_string(),
+        WeekLabel::Range => format!("{}..{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d")),
+    };
+    (start, label)
+}
+
+/// Start date and label of the fixed-length period containing a day
+///
+/// Periods are `days` long and aligned to `anchor`, which may lie before,
+/// inside or after the analyzed range.
+fn bucket_period(days: u32, anchor: NaiveDate, date: NaiveDate) -> (NaiveDate, String) {
+    let length = i64::from(days.max(1));
+    let index = (date - anchor).num_days().div_euclid(length);
+    let start = anchor + chrono::Duration::days(index * length);
+    (start, format!("Sprint {}", start.format("%Y-%m-%d")))
 }
 
 /// Collect activity statistics (commits by weekday and hour) from commits
 ///
-/// Groups commits by weekday (Mon-Sun) and hour (0-23) based on local timezone.
+/// Groups commits by weekday (Mon-Sun) and hour (0-23) in the given timezone.
 #[must_use]
-pub fn collect_activity_stats(commits: &[CommitInfo]) -> ActivityStats {
+pub fn collect_activity_stats(commits: &[CommitInfo], tz: Timezone) -> ActivityStats {
     let mut stats = ActivityStats::default();
 
     for commit in commits {
-        // Convert UTC timestamp to local timezone
-        let local_time = commit.timestamp.with_timezone(&Local);
+        let local_time = commit.local_time(tz);
 
         // chrono::Weekday: Mon=0, Tue=1, ..., Sun=6
         let weekday_index = local_time.weekday().num_days_from_monday() as usize;
@@ -169,8 +249,8 @@ pub fn collect_activity_stats(commits: &[CommitInfo]) -> ActivityStats {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::{DiffStats, FileChange};
-    use chrono::{TimeZone, Utc};
+    use crate::git::FileChange;
+    use chrono::TimeZone;
 
     fn make_commit(date: NaiveDate, additions: u64, deletions: u64) -> CommitInfo {
         let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).unwrap());
@@ -179,6 +259,7 @@ mod tests {
             timestamp,
             is_merge: false,
             diff: DiffStats::new(additions, deletions, 1),
+            ..Default::default()
         }
     }
 
@@ -189,7 +270,7 @@ mod tests {
             NaiveDate::from_ymd_opt(2024, 1, 3).unwrap(),
         );
 
-        let result = collect_stats("test", vec![], range, Period::Daily, None);
+        let result = collect_stats("test", &[], range, Period::Daily, Timezone::Utc);
 
         assert_eq!(result.repository, "test");
         assert_eq!(result.stats.len(), 3); // 3 days with zeros
@@ -208,7 +289,7 @@ mod tests {
         ];
 
         let range = DateRange::new(date1, date2);
-        let result = collect_stats("test", commits, range, Period::Daily, None);
+        let result = collect_stats("test", &commits, range, Period::Daily, Timezone::Utc);
 
         assert_eq!(result.stats.len(), 2);
         assert_eq!(result.total.commits, 3);
@@ -217,36 +298,82 @@ mod tests {
     }
 
     #[test]
-    fn test_collect_stats_with_extension_filter() {
+    fn test_collect_repository_stats_matches_combined_periods() {
+        let date1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let date2 = NaiveDate::from_ymd_opt(2024, 1, 9).unwrap();
+        let commits = vec![make_commit(date1, 10, 1), make_commit(date2, 20, 2)];
+        let range = DateRange::new(date1, date2);
+
+        let repo = collect_repository_stats("api", &commits, range, Period::Weekly, Timezone::Utc);
+        let combined = collect_stats("2 repos", &commits, range, Period::Weekly, Timezone::Utc);
+
+        assert_eq!(repo.name, "api");
+        let labels =
+            |stats: &[PeriodStats]| stats.iter().map(|s| s.label.clone()).collect::<Vec<_>>();
+        assert_eq!(labels(&repo.stats), labels(&combined.stats));
+        assert_eq!(repo.total.commits, 2);
+        assert_eq!(repo.total.additions, 30);
+    }
+
+    #[test]
+    fn test_collect_stats_counts_binary_files() {
         let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
         let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).unwrap());
 
         let mut diff = DiffStats::default();
-        diff.add_file(FileChange::new("src/main.rs".to_string(), 100, 10));
-        diff.add_file(FileChange::new("src/lib.ts".to_string(), 50, 5));
-        diff.add_file(FileChange::new("README.md".to_string(), 20, 2));
-
+        diff.add_file(FileChange::new("src/main.rs".to_string(), 10, 1));
+        diff.add_file(FileChange::binary("logo.png".to_string(), 100, 300));
         let commit = CommitInfo {
             id: "abc1234".to_string(),
             timestamp,
-            is_merge: false,
             diff,
+            ..Default::default()
         };
 
         let range = DateRange::new(date, date);
-        let extensions = vec!["rs".to_string()];
-        let result = collect_stats(
-            "test",
-            vec![commit],
-            range,
-            Period::Daily,
-            Some(&extensions),
-        );
+        let result = collect_stats("test", &[commit], range, Period::Daily, Timezone::Utc);
+        assert_eq!(result.total.files_changed, 2);
+        assert_eq!(result.total.binary_files, 1);
+        assert_eq!(result.total.binary_bytes, 200);
+    }
 
-        // Only .rs file should be counted
-        assert_eq!(result.total.additions, 100);
-        assert_eq!(result.total.deletions, 10);
-        assert_eq!(result.total.files_changed, 1);
+    #[test]
+    fn test_collect_author_stats() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let commits = vec![
+            make_commit(date, 100, 10)
+                .with_author("Alice".to_string(), "alice@example.com".to_string()),
+            make_commit(date, 30, 3).with_author("Bob".to_string(), "bob@example.com".to_string()),
+            make_commit(date, 50, 5)
+                .with_author("Alice".to_string(), "ALICE@example.com".to_string()),
+        ];
+
+        let authors = collect_author_stats(&commits);
+
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[0].name, "Alice");
+        assert_eq!(authors[0].commits, 2);
+        assert_eq!(authors[0].additions, 150);
+        assert_eq!(authors[0].deletions, 15);
+        assert_eq!(authors[0].net_lines, 135);
+        assert_eq!(authors[1].name, "Bob");
+        assert_eq!(authors[1].commits, 1);
+    }
+
+    #[test]
+    fn test_collect_author_stats_uses_latest_name() {
+        let old = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let new = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let commits = vec![
+            make_commit(new, 1, 0)
+                .with_author("Alice Smith".to_string(), "a@example.com".to_string()),
+            make_commit(old, 1, 0).with_author("alice".to_string(), "a@example.com".to_string()),
+        ];
+
+        let authors = collect_author_stats(&commits);
+
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name, "Alice Smith");
     }
 
     #[test]
@@ -280,7 +407,7 @@ mod tests {
             },
         ];
 
-        let weekly = aggregate_by_week(daily);
+        let weekly = aggregate(daily, |date| calendar_period(Period::Weekly, date));
 
         assert_eq!(weekly.len(), 2);
         // First week: 2 + 3 commits
@@ -309,17 +436,164 @@ mod tests {
             },
         ];
 
-        let monthly = aggregate_by_month(daily);
+        let monthly = aggregate(daily, |date| calendar_period(Period::Monthly, date));
 
         assert_eq!(monthly.len(), 2);
         assert!(monthly[0].label.contains("2024-01"));
         assert!(monthly[1].label.contains("2024-02"));
     }
 
+    #[test]
+    fn test_calendar_period_quarters() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+
+        assert_eq!(
+            calendar_period(Period::Quarterly, date(8, 20)),
+            (date(7, 1), "2026-Q3".to_string())
+        );
+        assert_eq!(
+            calendar_period(Period::Quarterly, date(12, 31)),
+            (date(10, 1), "2026-Q4".to_string())
+        );
+        assert_eq!(
+            calendar_period(Period::Quarterly, date(1, 1)),
+            (date(1, 1), "2026-Q1".to_string())
+        );
+        assert_eq!(
+            calendar_period(Period::Weekly, date(10, 17)),
+            (date(10, 12), "2026-W42".to_string())
+        );
+    }
+
+    #[test]
+    fn test_week_period_start_and_labels() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        // Saturday 2026-10-17
+        let saturday = date(10, 17);
+
+        assert_eq!(
+            week_period(WeekStart::Monday, WeekLabel::Iso, saturday),
+            (date(10, 12), "2026-W42".to_string())
+        );
+        assert_eq!(
+            week_period(WeekStart::Sunday, WeekLabel::StartDate, saturday),
+            (date(10, 11), "2026-10-11".to_string())
+        );
+        assert_eq!(
+            week_period(WeekStart::Sunday, WeekLabel::Range, saturday),
+            (date(10, 11), "2026-10-11..2026-10-17".to_string())
+        );
+        // A Sunday opens the next Sunday-start week, numbered by its Monday
+        assert_eq!(
+            week_period(WeekStart::Sunday, WeekLabel::Iso, date(10, 18)),
+            (date(10, 18), "2026-W43".to_string())
+        );
+        assert_eq!(
+            week_period(WeekStart::Monday, WeekLabel::Iso, date(10, 18)).1,
+            "2026-W42"
+        );
+    }
+
+    #[test]
+    fn test_periods_are_dated_by_their_start() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let commits = vec![make_commit(date(10, 15), 10, 0)];
+        // Range starts on a Wednesday, mid-month and mid-quarter
+        let range = DateRange::new(date(10, 14), date(10, 24));
+
+        let sunday_weeks = Grouping::Calendar {
+            period: Period::Weekly,
+            week_start: WeekStart::Sunday,
+            week_label: WeekLabel::StartDate,
+        };
+        let result = collect_stats("test", &commits, range, sunday_weeks, Timezone::Utc);
+        let periods: Vec<_> = result
+            .stats
+            .iter()
+            .map(|s| (s.date, s.label.as_str(), s.commits))
+            .collect();
+        assert_eq!(
+            periods,
+            [
+                (date(10, 11), "2026-10-11", 1),
+                (date(10, 18), "2026-10-18", 0)
+            ]
+        );
+
+        for (period, start) in [
+            (Period::Weekly, date(10, 12)),
+            (Period::Monthly, date(10, 1)),
+            (Period::Quarterly, date(10, 1)),
+            (Period::Yearly, date(1, 1)),
+        ] {
+            let result = collect_stats("test", &commits, range, period, Timezone::Utc);
+            assert_eq!(result.stats[0].date, start, "{period}");
+        }
+    }
+
+    #[test]
+    fn test_bucket_period_aligns_to_anchor() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let anchor = date(10, 5);
+
+        assert_eq!(
+            bucket_period(14, anchor, date(10, 5)),
+            (date(10, 5), "Sprint 2026-10-05".to_string())
+        );
+        assert_eq!(bucket_period(14, anchor, date(10, 18)).0, date(10, 5));
+        assert_eq!(bucket_period(14, anchor, date(10, 19)).0, date(10, 19));
+        // Days before the anchor fall into earlier sprints
+        assert_eq!(bucket_period(14, anchor, date(10, 4)).0, date(9, 21));
+    }
+
+    #[test]
+    fn test_collect_stats_quarterly_and_buckets() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let commits = vec![
+            make_commit(date(9, 30), 10, 0),
+            make_commit(date(10, 1), 20, 0),
+            make_commit(date(10, 20), 30, 0),
+        ];
+        let range = DateRange::new(date(9, 28), date(10, 31));
+
+        let result = collect_stats("test", &commits, range, Period::Quarterly, Timezone::Utc);
+        assert_eq!(result.period, "quarterly");
+        let labels: Vec<_> = result.stats.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["2026-Q3", "2026-Q4"]);
+        assert_eq!(result.stats[1].additions, 50);
+
+        let sprints = Grouping::Buckets {
+            days: 14,
+            start: Some(date(10, 5)),
+        };
+        let result = collect_stats("test", &commits, range, sprints, Timezone::Utc);
+        assert_eq!(result.period, "14d");
+        let labels: Vec<_> = result.stats.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            [
+                "Sprint 2026-09-21",
+                "Sprint 2026-10-05",
+                "Sprint 2026-10-19"
+            ]
+        );
+        assert_eq!(result.stats[0].additions, 30);
+        assert_eq!(result.stats[2].additions, 30);
+        assert_eq!(result.total.commits, 3);
+
+        // Without a start date, sprints begin on the first day of the range
+        let sprints = Grouping::Buckets {
+            days: 14,
+            start: None,
+        };
+        let result = collect_stats("test", &commits, range, sprints, Timezone::Utc);
+        assert_eq!(result.stats[0].label, "Sprint 2026-09-28");
+    }
+
     #[test]
     fn test_collect_activity_stats_empty() {
         let commits: Vec<CommitInfo> = vec![];
-        let stats = collect_activity_stats(&commits);
+        let stats = collect_activity_stats(&commits, Timezone::Local);
 
         assert_eq!(stats.weekday, [0; 7]);
         assert_eq!(stats.hourly, [0; 24]);
@@ -335,9 +609,10 @@ mod tests {
             timestamp,
             is_merge: false,
             diff: DiffStats::default(),
+            ..Default::default()
         };
 
-        let stats = collect_activity_stats(&[commit]);
+        let stats = collect_activity_stats(&[commit], Timezone::Local);
 
         // Verify exactly one commit is counted across all weekdays and hours
         let total_weekday: u32 = stats.weekday.iter().sum();
@@ -350,6 +625,36 @@ mod tests {
         assert_eq!(stats.hourly.iter().filter(|&&x| x == 1).count(), 1);
     }
 
+    #[test]
+    fn test_collect_stats_and_activity_share_timezone() {
+        // 14:30 UTC on Sunday is 23:30 in Tokyo, 09:30 at the author's -05:00
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(14, 30, 0).unwrap());
+        let commit = CommitInfo::new("a".to_string(), timestamp, false, DiffStats::default())
+            .with_offset_minutes(-300);
+        let range = DateRange::new(date, date);
+
+        for (tz, hour) in [
+            (Timezone::Utc, 14),
+            (Timezone::Named(chrono_tz::Asia::Tokyo), 23),
+            (Timezone::Author, 9),
+        ] {
+            let result = collect_stats(
+                "test",
+                std::slice::from_ref(&commit),
+                range,
+                Period::Daily,
+                tz,
+            );
+            assert_eq!(result.total.commits, 1);
+            assert_eq!(result.stats[0].date, date);
+
+            let activity = collect_activity_stats(std::slice::from_ref(&commit), tz);
+            assert_eq!(activity.hourly[hour], 1, "{tz}");
+            assert_eq!(activity.weekday[6], 1, "{tz}");
+        }
+    }
+
     #[test]
     fn test_collect_activity_stats_multiple_commits() {
         let commits: Vec<CommitInfo> = vec![
@@ -362,6 +667,7 @@ mod tests {
                     timestamp,
                     is_merge: false,
                     diff: DiffStats::default(),
+                    ..Default::default()
                 }
             },
             {
@@ -372,6 +678,7 @@ mod tests {
                     timestamp,
                     is_merge: false,
                     diff: DiffStats::default(),
+                    ..Default::default()
                 }
             },
             // Another commit at a different time
@@ -383,6 +690,7 @@ mod tests {
                     timestamp,
                     is_merge: false,
                     diff: DiffStats::default(),
+                    ..Default::default()
                 }
             },
             // Late night commit
@@ -394,11 +702,12 @@ mod tests {
                     timestamp,
                     is_merge: false,
                     diff: DiffStats::default(),
+                    ..Default::default()
                 }
             },
         ];
 
-        let stats = collect_activity_stats(&commits);
+        let stats = collect_activity_stats(&commits, Timezone::Local);
 
         // Verify total commits are counted correctly
         let total_weekday: u32 = stats.weekday.iter().sum();
@@ -0,0 +1,302 @@
+//! Date expression parsing for `--from` / `--to`
+//!
+//! Supported forms (case-insensitive):
+//!
+//! - Absolute dates: `2026-01-01`
+//! - `today`, `yesterday`
+//! - `N <unit>s ago` / `a <unit> ago` (unit: day, week, month, quarter, year)
+//! - `last <weekday>` (the most recent such day before today)
+//! - `start of [this|last] <period>` / `end of [this|last] <period>`
+//!   (period: week, month, quarter, year; weeks begin on the configured week start)
+
+use crate::error::{Error, Result};
+use crate::stats::WeekStart;
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+/// Calendar unit used in relative expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Unit {
+    fn parse(word: &str) -> Option<Self> {
+        match word.strip_suffix('s').unwrap_or(word) {
+            "day" => Some(Self::Day),
+            "week" => Some(Self::Week),
+            "month" => Some(Self::Month),
+            "quarter" => Some(Self::Quarter),
+            "year" => Some(Self::Year),
+            _ => None,
+        }
+    }
+
+    /// Move `date` back by `n` units
+    fn sub(self, date: NaiveDate, n: u32) -> Option<NaiveDate> {
+        match self {
+            Self::Day => date.checked_sub_days(Days::new(u64::from(n))),
+            Self::Week => date.checked_sub_days(Days::new(u64::from(n) * 7)),
+            Self::Month => date.checked_sub_months(Months::new(n)),
+            Self::Quarter => date.checked_sub_months(Months::new(n.checked_mul(3)?)),
+            Self::Year => date.checked_sub_months(Months::new(n.checked_mul(12)?)),
+        }
+    }
+
+    /// First day of the period containing `date`
+    fn start_of(self, date: NaiveDate, week_start: WeekStart) -> Option<NaiveDate> {
+        match self {
+            Self::Day => Some(date),
+            Self::Week => date.checked_sub_days(Days::new(u64::from(
+                week_start.days_into_week(date.weekday()),
+            ))),
+            Self::Month => date.with_day(1),
+            Self::Quarter => NaiveDate::from_ymd_opt(date.year(), (date.month0() / 3) * 3 + 1, 1),
+            Self::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1),
+        }
+    }
+
+    /// Last day of the period containing `date`
+    fn end_of(self, date: NaiveDate, week_start: WeekStart) -> Option<NaiveDate> {
+        let start = self.start_of(date, week_start)?;
+        let next = match self {
+            Self::Day => start.checked_add_days(Days::new(1)),
+            Self::Week => start.checked_add_days(Days::new(7)),
+            Self::Month => start.checked_add_months(Months::new(1)),
+            Self::Quarter => start.checked_add_months(Months::new(3)),
+            Self::Year => start.checked_add_months(Months::new(12)),
+        }?;
+        next.pred_opt()
+    }
+}
+
+/// Parse a date expression relative to `today`
+///
+/// `start of week` / `end of week` use weeks beginning on `week_start`.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidDate` if the expression is not recognized or
+/// the resulting date is out of range
+pub fn parse_date(input: &str, today: NaiveDate, week_start: WeekStart) -> Result<NaiveDate> {
+    let normalized = input.trim().to_lowercase();
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+
+    let parsed = match words.as_slice() {
+        [] => return Err(invalid(input, "empty date")),
+        [word] if word.contains('-') => {
+            return NaiveDate::parse_from_str(word, "%Y-%m-%d")
+                .map_err(|_| invalid(input, "expected YYYY-MM-DD"));
+        }
+        ["today"] => Some(today),
+        ["yesterday"] => today.pred_opt(),
+        [count, unit, "ago"] => {
+            let n = parse_count(count).ok_or_else(|| invalid(input, "invalid count"))?;
+            let unit = Unit::parse(unit).ok_or_else(|| invalid(input, "unknown unit"))?;
+            unit.sub(today, n)
+        }
+        ["last", day] => {
+            let weekday: Weekday = day
+                .parse()
+                .map_err(|_| invalid(input, "expected a weekday after 'last'"))?;
+            let back =
+                (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+            let back = if back == 0 { 7 } else { back };
+            today.checked_sub_days(Days::new(u64::from(back)))
+        }
+        [edge @ ("start" | "end"), "of", rest @ ..] => {
+            let (offset, unit) = match rest {
+                [unit] | ["this", unit] => (0, *unit),
+                ["last", unit] => (1, *unit),
+                _ => return Err(invalid(input, "expected 'start of [this|last] <period>'")),
+            };
+            let unit = Unit::parse(unit)
+                .filter(|u| *u != Unit::Day)
+                .ok_or_else(|| invalid(input, "expected week, month, quarter or year"))?;
+            let anchor = unit
+                .start_of(today, week_start)
+                .and_then(|d| unit.sub(d, offset));
+            if *edge == "start" {
+                anchor.and_then(|d| unit.start_of(d, week_start))
+            } else {
+                anchor.and_then(|d| unit.end_of(d, week_start))
+            }
+        }
+        _ => return Err(invalid(input, "unrecognized date expression")),
+    };
+
+    parsed.ok_or_else(|| invalid(input, "date out of range"))
+}
+
+fn parse_count(word: &str) -> Option<u32> {
+    match word {
+        "a" | "an" | "one" => Some(1),
+        _ => word.parse().ok(),
+    }
+}
+
+fn invalid(input: &str, message: &str) -> Error {
+    Error::InvalidDate {
+        input: input.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    // Wednesday
+    fn today() -> NaiveDate {
+        date(2026, 8, 12)
+    }
+
+    #[test]
+    fn test_parse_absolute_date() {
+        assert_eq!(
+            parse_date("2026-01-01", today(), WeekStart::Monday).unwrap(),
+            date(2026, 1, 1)
+        );
+        assert!(matches!(
+            parse_date("2026-13-01", today(), WeekStart::Monday),
+            Err(Error::InvalidDate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_today_yesterday() {
+        assert_eq!(
+            parse_date("today", today(), WeekStart::Monday).unwrap(),
+            today()
+        );
+        assert_eq!(
+            parse_date(" Yesterday ", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 11)
+        );
+    }
+
+    #[test]
+    fn test_parse_ago() {
+        assert_eq!(
+            parse_date("3 days ago", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 9)
+        );
+        assert_eq!(
+            parse_date("2 weeks ago", today(), WeekStart::Monday).unwrap(),
+            date(2026, 7, 29)
+        );
+        assert_eq!(
+            parse_date("a month ago", today(), WeekStart::Monday).unwrap(),
+            date(2026, 7, 12)
+        );
+        assert_eq!(
+            parse_date("1 quarter ago", today(), WeekStart::Monday).unwrap(),
+            date(2026, 5, 12)
+        );
+        assert_eq!(
+            parse_date("1 year ago", today(), WeekStart::Monday).unwrap(),
+            date(2025, 8, 12)
+        );
+    }
+
+    #[test]
+    fn test_parse_last_weekday() {
+        assert_eq!(
+            parse_date("last monday", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 10)
+        );
+        assert_eq!(
+            parse_date("last tuesday", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 11)
+        );
+        // Same weekday as today means a week ago
+        assert_eq!(
+            parse_date("last wednesday", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 5)
+        );
+        assert_eq!(
+            parse_date("last sun", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 9)
+        );
+    }
+
+    #[test]
+    fn test_parse_start_and_end_of_period() {
+        assert_eq!(
+            parse_date("start of week", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 10)
+        );
+        assert_eq!(
+            parse_date("end of week", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 16)
+        );
+        assert_eq!(
+            parse_date("start of month", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 1)
+        );
+        assert_eq!(
+            parse_date("end of month", today(), WeekStart::Monday).unwrap(),
+            date(2026, 8, 31)
+        );
+        assert_eq!(
+            parse_date("start of quarter", today(), WeekStart::Monday).unwrap(),
+            date(2026, 7, 1)
+        );
+        assert_eq!(
+            parse_date("end of this quarter", today(), WeekStart::Monday).unwrap(),
+            date(2026, 9, 30)
+        );
+        assert_eq!(
+            parse_date("start of last quarter", today(), WeekStart::Monday).unwrap(),
+            date(2026, 4, 1)
+        );
+        assert_eq!(
+            parse_date("end of last quarter", today(), WeekStart::Monday).unwrap(),
+            date(2026, 6, 30)
+        );
+        assert_eq!(
+            parse_date("start of year", today(), WeekStart::Monday).unwrap(),
+            date(2026, 1, 1)
+        );
+        assert_eq!(
+            parse_date("end of last year", today(), WeekStart::Monday).unwrap(),
+            date(2025, 12, 31)
+        );
+    }
+
+    #[test]
+    fn test_parse_week_follows_week_start() {
+        let sunday = |input| parse_date(input, today(), WeekStart::Sunday).unwrap();
+        assert_eq!(sunday("start of week"), date(2026, 8, 9));
+        assert_eq!(sunday("end of week"), date(2026, 8, 15));
+        assert_eq!(sunday("start of last week"), date(2026, 8, 2));
+        assert_eq!(sunday("end of last week"), date(2026, 8, 8));
+    }
+
+    #[test]
+    fn test_parse_invalid_expressions() {
+        for input in [
+            "",
+            "soon",
+            "two days ago",
+            "3 fortnights ago",
+            "last friyay",
+            "start of day",
+        ] {
+            assert!(
+                matches!(
+                    parse_date(input, today(), WeekStart::Monday),
+                    Err(Error::InvalidDate { .. })
+                ),
+                "expected error for {input:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Commit filtering logic
+
+use crate::error::{Error, Result};
+use crate::git::CommitInfo;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use regex::{Regex, RegexBuilder};
+use std::path::Path;
+
+/// Filter commits by author name or email
+///
+/// Each pattern is a case-insensitive regular expression matched against the
+/// author signature (`Name <email>`), so a plain string acts as a substring match.
+/// A commit is kept if any pattern matches.
+#[derive(Debug, Clone, Default)]
+pub struct AuthorFilter {
+    patterns: Vec<Regex>,
+}
+
+impl AuthorFilter {
+    /// Compile author patterns into a filter
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any pattern is not a valid regular expression
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| Error::InvalidPattern {
+                        pattern: pattern.clone(),
+                        message: e.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { patterns })
+    }
+
+    /// Check if a commit's author matches any pattern
+    #[must_use]
+    pub fn matches(&self, commit: &CommitInfo) -> bool {
+        if self.patterns.is_empty() {
+            return true;
+        }
+
+        let signature = commit.author_signature();
+        self.patterns.iter().any(|p| p.is_match(&signature))
+    }
+}
+
+/// Filter changed files by gitignore-style path globs
+///
+/// A file is kept if it matches any include pattern (or there are none) and
+/// does not match an exclude pattern. Patterns follow `.gitignore` semantics:
+/// `*.lock` matches at any depth, `/docs` only at the repository root, and a
+/// directory pattern such as `vendor/` covers everything beneath it.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    include: Gitignore,
+    exclude: Gitignore,
+}
+
+impl PathFilter {
+    /// Compile include and exclude globs into a filter
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any pattern is not a valid glob
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: build_globs(include)?,
+            exclude: build_globs(exclude)?,
+        })
+    }
+
+    /// Check if the filter has no patterns (keeps every file)
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Check if a repository-relative path passes the filter
+    #[must_use]
+    pub fn matches(&self, path: &str) -> bool {
+        let path = Path::new(path);
+
+        if !self.include.is_empty()
+            && !self
+                .include
+                .matched_path_or_any_parents(path, false)
+                .is_ignore()
+        {
+            return false;
+        }
+
+        !self
+            .exclude
+            .matched_path_or_any_parents(path, false)
+            .is_ignore()
+    }
+}
+
+fn build_globs(patterns: &[String]) -> Result<Gitignore> {
+    let invalid = |pattern: &str, e: ignore::Error| Error::InvalidPattern {
+        pattern: pattern.to_string(),
+        message: e.to_string(),
+    };
+
+    let mut builder = GitignoreBuilder::new("");
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .map_err(|e| invalid(pattern, e))?;
+    }
+    builder
+        .build()
+        .map_err(|e| invalid(&patterns.join(", "), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_by(name: &str, email: &str) -> CommitInfo {
+        CommitInfo::default().with_author(name.to_string(), email.to_string())
+    }
+
+    #[test]
+    fn test_author_filter_empty_matches_all() {
+        let filter = AuthorFilter::new(&[]).unwrap();
+        assert!(filter.matches(&commit_by("Alice", "alice@example.com")));
+    }
+
+    #[test]
+    fn test_author_filter_substring() {
+        let filter = AuthorFilter::new(&["alice".to_string()]).unwrap();
+
+        assert!(filter.matches(&commit_by("Alice Smith", "a.smith@example.com")));
+        assert!(filter.matches(&commit_by("A. Smith", "alice@example.com")));
+        assert!(!filter.matches(&commit_by("Bob", "bob@example.com")));
+    }
+
+    #[test]
+    fn test_author_filter_regex_and_multiple_patterns() {
+        let filter =
+            AuthorFilter::new(&["^bob ".to_string(), "@corp\\.example>$".to_string()]).unwrap();
+
+        assert!(filter.matches(&commit_by("Bob", "bob@example.com")));
+        assert!(!filter.matches(&commit_by("Bobby", "bobby@example.com")));
+        assert!(filter.matches(&commit_by("Carol", "carol@corp.example")));
+        assert!(!filter.matches(&commit_by("Carol", "carol@corp.example.org")));
+    }
+
+    #[test]
+    fn test_author_filter_invalid_regex() {
+        let result = AuthorFilter::new(&["(unclosed".to_string()]);
+        assert!(matches!(result, Err(Error::InvalidPattern { .. })));
+    }
+
+    fn strings(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_path_filter_empty_matches_all() {
+        let filter = PathFilter::new(&[], &[]).unwrap();
+        assert!(filter.is_empty());
+        assert!(filter.matches("src/main.rs"));
+    }
+
+    #[test]
+    fn test_path_filter_include() {
+        let filter = PathFilter::new(&strings(&["src/", "*.toml"]), &[]).unwrap();
+
+        assert!(filter.matches("src/main.rs"));
+        assert!(filter.matches("src/git/diff.rs"));
+        assert!(filter.matches("Cargo.toml"));
+        assert!(filter.matches("crates/core/Cargo.toml"));
+        assert!(!filter.matches("README.md"));
+        assert!(!filter.matches("tests/src.rs"));
+    }
+
+    #[test]
+    fn test_path_filter_exclude_wins_over_include() {
+        let filter =
+            PathFilter::new(&strings(&["src/**"]), &strings(&["*.snap", "/src/gen"])).unwrap();
+
+        assert!(filter.matches("src/lib.rs"));
+        assert!(!filter.matches("src/snapshots/table.snap"));
+        assert!(!filter.matches("src/gen/schema.rs"));
+        assert!(!filter.matches("docs/guide.md"));
+    }
+
+    #[test]
+    fn test_path_filter_exclude_only() {
+        let filter = PathFilter::new(&[], &strings(&["Cargo.lock", "vendor/"])).unwrap();
+
+        assert!(filter.matches("src/main.rs"));
+        assert!(!filter.matches("Cargo.lock"));
+        assert!(!filter.matches("vendor/lib/x.c"));
+        assert!(!filter.matches("third_party/vendor/y.c"));
+    }
+
+    #[test]
+    fn test_path_filter_invalid_glob() {
+        let result = PathFilter::new(&strings(&["{src,docs"]), &[]);
+        assert!(matches!(result, Err(Error::InvalidPattern { .. })));
+    }
+}
@@ -0,0 +1,29 @@
+//! Metric groups selected for a run
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Group of output metrics that can be selected with `--metrics`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricGroup {
+    /// Number of commits
+    Commits,
+    /// Lines added, deleted and net change
+    Lines,
+    /// Number of files changed
+    Files,
+    /// Binary files changed and their net size change
+    Binary,
+}
+
+impl MetricGroup {
+    /// Every metric, in output column order
+    pub const ALL: [Self; 4] = [Self::Commits, Self::Lines, Self::Files, Self::Binary];
+
+    /// Whether this metric needs each commit's diff
+    #[must_use]
+    pub const fn needs_diff(self) -> bool {
+        !matches!(self, Self::Commits)
+    }
+}
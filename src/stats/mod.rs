@@ -2,8 +2,21 @@
 
 pub mod aggregator;
 pub mod collector;
+pub mod date_expr;
+pub mod filter;
+pub mod metric;
+pub mod period;
 pub mod types;
 
 pub use aggregator::{filter_non_zero, merge_stats, running_totals};
-pub use collector::{collect_activity_stats, collect_stats};
-pub use types::{ActivityStats, AnalysisResult, DateRange, Days, PeriodStats, TotalStats};
+pub use collector::{
+    collect_activity_stats, collect_author_stats, collect_repository_stats, collect_stats,
+};
+pub use date_expr::parse_date;
+pub use filter::{AuthorFilter, PathFilter};
+pub use metric::MetricGroup;
+pub use period::{Period, WeekLabel, WeekStart};
+pub use types::{
+    ActivityStats, AnalysisResult, AuthorStats, DateRange, Days, Grouping, PeriodStats,
+    RepositoryStats, TotalStats,
+};
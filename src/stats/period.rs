@@ -0,0 +1,129 @@
+//! Aggregation periods and how weekly periods are shown
+
+use chrono::Weekday;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Time period for aggregation
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    /// Aggregate by day
+    #[default]
+    Daily,
+    /// Aggregate by week
+    Weekly,
+    /// Aggregate by month
+    Monthly,
+    /// Aggregate by calendar quarter
+    Quarterly,
+    /// Aggregate by year
+    Yearly,
+}
+
+impl std::fmt::Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Daily => write!(f, "daily"),
+            Self::Weekly => write!(f, "weekly"),
+            Self::Monthly => write!(f, "monthly"),
+            Self::Quarterly => write!(f, "quarterly"),
+            Self::Yearly => write!(f, "yearly"),
+        }
+    }
+}
+
+/// First day of a weekly period
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    /// Weeks run Monday to Sunday (ISO 8601)
+    #[default]
+    Monday,
+    /// Weeks run Sunday to Saturday
+    Sunday,
+}
+
+impl WeekStart {
+    /// Days from the start of the week to `weekday` (0 on the first day)
+    #[must_use]
+    pub fn days_into_week(self, weekday: Weekday) -> u32 {
+        match self {
+            Self::Monday => weekday.num_days_from_monday(),
+            Self::Sunday => weekday.num_days_from_sunday(),
+        }
+    }
+}
+
+impl std::fmt::Display for WeekStart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Monday => write!(f, "monday"),
+            Self::Sunday => write!(f, "sunday"),
+        }
+    }
+}
+
+/// How weekly periods are labeled
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WeekLabel {
+    /// ISO week number, e.g. 2026-W42
+    #[default]
+    Iso,
+    /// First day of the week, e.g. 2026-10-12
+    StartDate,
+    /// First and last day of the week, e.g. 2026-10-12..2026-10-18
+    Range,
+}
+
+impl std::fmt::Display for WeekLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Iso => write!(f, "iso"),
+            Self::StartDate => write!(f, "start-date"),
+            Self::Range => write!(f, "range"),
+        }
+    }
+}
+
+/// Parse a `--bucket` length like `14d` or `2w` into days
+pub(crate) fn parse_bucket(value: &str) -> Result<u32, String> {
+    let invalid = || format!("invalid bucket length '{value}' (expected e.g. 14d or 2w)");
+    let (count, days_per_unit) = if let Some(count) = value.strip_suffix('d') {
+        (count, 1)
+    } else if let Some(count) = value.strip_suffix('w') {
+        (count, 7)
+    } else {
+        return Err(invalid());
+    };
+    count
+        .parse::<u32>()
+        .ok()
+        .and_then(|count| count.checked_mul(days_per_unit))
+        .filter(|&days| days > 0)
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_period_display() {
+        assert_eq!(Period::Daily.to_string(), "daily");
+        assert_eq!(Period::Weekly.to_string(), "weekly");
+        assert_eq!(Period::Monthly.to_string(), "monthly");
+        assert_eq!(Period::Quarterly.to_string(), "quarterly");
+        assert_eq!(Period::Yearly.to_string(), "yearly");
+    }
+
+    #[test]
+    fn test_parse_bucket() {
+        assert_eq!(parse_bucket("14d"), Ok(14));
+        assert_eq!(parse_bucket("2w"), Ok(14));
+        for invalid in ["14", "0d", "d", "-1w", "2 weeks"] {
+            assert!(parse_bucket(invalid).is_err());
+        }
+    }
+}
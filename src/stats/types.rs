@@ -2,7 +2,10 @@
 
 #![allow(clippy::cast_possible_wrap)]
 
-use chrono::{Local, NaiveDate};
+use crate::error::RepoWarning;
+use crate::git::{DiffStats, Timezone};
+use crate::stats::{MetricGroup, Period, WeekLabel, WeekStart};
+use chrono::NaiveDate;
 use serde::Serialize;
 
 /// Days count (non-negative)
@@ -29,6 +32,46 @@ impl From<u32> for Days {
     }
 }
 
+/// How days are grouped into reported periods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    /// Calendar periods (days, weeks, months, quarters or years)
+    Calendar {
+        /// Period length
+        period: Period,
+        /// First day of weekly periods
+        week_start: WeekStart,
+        /// Label of weekly periods
+        week_label: WeekLabel,
+    },
+    /// Fixed-length periods counted from an anchor date
+    Buckets {
+        /// Length of each period in days
+        days: u32,
+        /// Start of one of the periods (defaults to the start of the range)
+        start: Option<NaiveDate>,
+    },
+}
+
+impl From<Period> for Grouping {
+    fn from(period: Period) -> Self {
+        Self::Calendar {
+            period,
+            week_start: WeekStart::default(),
+            week_label: WeekLabel::default(),
+        }
+    }
+}
+
+impl std::fmt::Display for Grouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Calendar { period, .. } => write!(f, "{period}"),
+            Self::Buckets { days, .. } => write!(f, "{days}d"),
+        }
+    }
+}
+
 /// Date range for analysis
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DateRange {
@@ -41,10 +84,10 @@ pub struct DateRange {
 impl DateRange {
     /// Create a date range for the last N days (including today)
     ///
-    /// Uses local timezone to determine "today"
+    /// "Today" is determined in the given timezone
     #[must_use]
-    pub fn last_n_days(days: Days) -> Self {
-        let to = Local::now().date_naive();
+    pub fn last_n_days(days: Days, tz: Timezone) -> Self {
+        let to = tz.today();
         let from = to - chrono::Duration::days(i64::from(days.0));
         Self { from, to }
     }
@@ -55,6 +98,19 @@ impl DateRange {
         Self { from, to }
     }
 
+    /// Create the smallest range covering all given dates
+    ///
+    /// Returns `None` if there are no dates.
+    #[must_use]
+    pub fn spanning(dates: impl IntoIterator<Item = NaiveDate>) -> Option<Self> {
+        dates.into_iter().fold(None, |range, date| {
+            Some(match range {
+                None => Self::new(date, date),
+                Some(r) => Self::new(r.from.min(date), r.to.max(date)),
+            })
+        })
+    }
+
     /// Check if a date falls within this range
     #[must_use]
     pub fn contains(&self, date: NaiveDate) -> bool {
@@ -96,6 +152,12 @@ pub struct PeriodStats {
 
     /// Number of files changed
     pub files_changed: u32,
+
+    /// Number of binary files changed
+    pub binary_files: u32,
+
+    /// Bytes changed in binary files
+    pub binary_bytes: u64,
 }
 
 // serde's serialize_with requires `fn(&T, S)` signature
@@ -118,7 +180,7 @@ impl PeriodStats {
         }
     }
 
-    /// Create with a custom label (for weekly/monthly/yearly)
+    /// Create with a custom label (for periods longer than a day)
     #[must_use]
     pub fn with_label(date: NaiveDate, label: String) -> Self {
         Self {
@@ -140,9 +202,22 @@ impl PeriodStats {
         self.additions += other.additions;
         self.deletions += other.deletions;
         self.files_changed += other.files_changed;
+        self.binary_files += other.binary_files;
+        self.binary_bytes += other.binary_bytes;
         self.net_lines = self.calculate_net_lines();
     }
 
+    /// Add a single commit's diff totals to this period
+    pub fn add_commit(&mut self, diff: &DiffStats) {
+        self.commits += 1;
+        self.additions += diff.additions;
+        self.deletions += diff.deletions;
+        self.files_changed += diff.files_changed;
+        self.binary_files += diff.binary_files;
+        self.binary_bytes += diff.binary_bytes;
+        self.update_net_lines();
+    }
+
     /// Update `net_lines` based on current additions/deletions
     pub fn update_net_lines(&mut self) {
         self.net_lines = self.calculate_net_lines();
@@ -171,6 +246,25 @@ pub struct AnalysisResult {
 
     /// Total statistics across all periods
     pub total: TotalStats,
+
+    /// Per-author breakdown (only populated when requested)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<AuthorStats>,
+
+    /// Per-repository breakdown (only populated when several repositories are analyzed)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repositories: Vec<RepositoryStats>,
+
+    /// Commits visited during the walk but outside the date range
+    pub out_of_range_commits: usize,
+
+    /// Repositories that failed and were left out of the results
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<RepoWarning>,
+
+    /// Metrics that were computed; formatters omit the others
+    #[serde(skip)]
+    pub metrics: Vec<MetricGroup>,
 }
 
 impl AnalysisResult {
@@ -191,8 +285,77 @@ impl AnalysisResult {
             to,
             stats,
             total,
+            authors: Vec::new(),
+            repositories: Vec::new(),
+            out_of_range_commits: 0,
+            warnings: Vec::new(),
+            metrics: MetricGroup::ALL.to_vec(),
+        }
+    }
+}
+
+/// Statistics for a single repository within a multi-repository analysis
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct RepositoryStats {
+    /// Repository name
+    pub name: String,
+
+    /// Statistics per period, over the same periods as the combined series
+    pub stats: Vec<PeriodStats>,
+
+    /// Total statistics across all periods
+    pub total: TotalStats,
+}
+
+impl RepositoryStats {
+    /// Create repository statistics, computing the totals from the periods
+    #[must_use]
+    pub fn new(name: String, stats: Vec<PeriodStats>) -> Self {
+        let total = TotalStats::from_periods(&stats);
+        Self { name, stats, total }
+    }
+}
+
+/// Statistics for a single author
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct AuthorStats {
+    /// Author name
+    pub name: String,
+
+    /// Author email
+    pub email: String,
+
+    /// Number of commits
+    pub commits: u32,
+
+    /// Lines added
+    pub additions: u64,
+
+    /// Lines deleted
+    pub deletions: u64,
+
+    /// Net line change (additions - deletions)
+    pub net_lines: i64,
+
+    /// Number of files changed
+    pub files_changed: u32,
+}
+
+impl AuthorStats {
+    /// Create empty statistics for an author
+    #[must_use]
+    pub fn new(name: String, email: String) -> Self {
+        Self {
+            name,
+            email,
+            ..Default::default()
         }
     }
+
+    /// Update `net_lines` based on current additions/deletions
+    pub fn update_net_lines(&mut self) {
+        self.net_lines = self.additions as i64 - self.deletions as i64;
+    }
 }
 
 /// Activity statistics by weekday and hour
@@ -235,6 +398,12 @@ pub struct TotalStats {
 
     /// Total files changed
     pub files_changed: u32,
+
+    /// Total binary files changed
+    pub binary_files: u32,
+
+    /// Total bytes changed in binary files
+    pub binary_bytes: u64,
 }
 
 impl TotalStats {
@@ -247,6 +416,8 @@ impl TotalStats {
             total.additions += p.additions;
             total.deletions += p.deletions;
             total.files_changed += p.files_changed;
+            total.binary_files += p.binary_files;
+            total.binary_bytes += p.binary_bytes;
         }
         total.net_lines = total.additions as i64 - total.deletions as i64;
         total
@@ -256,6 +427,7 @@ impl TotalStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Local;
 
     #[test]
     fn test_days_creation() {
@@ -266,9 +438,19 @@ mod tests {
         assert_eq!(days.value(), 30);
     }
 
+    #[test]
+    fn test_grouping_display() {
+        assert_eq!(Grouping::from(Period::Quarterly).to_string(), "quarterly");
+        let sprints = Grouping::Buckets {
+            days: 14,
+            start: None,
+        };
+        assert_eq!(sprints.to_string(), "14d");
+    }
+
     #[test]
     fn test_date_range_last_n_days() {
-        let range = DateRange::last_n_days(Days::new(7));
+        let range = DateRange::last_n_days(Days::new(7), Timezone::Local);
         let today = Local::now().date_naive();
 
         assert_eq!(range.to, today);
@@ -288,6 +470,17 @@ mod tests {
         assert!(!range.contains(NaiveDate::from_ymd_opt(2024, 2, 1).unwrap()));
     }
 
+    #[test]
+    fn test_date_range_spanning() {
+        let d = |day| NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+
+        assert_eq!(DateRange::spanning([]), None);
+        assert_eq!(
+            DateRange::spanning([d(5), d(2), d(9), d(3)]),
+            Some(DateRange::new(d(2), d(9)))
+        );
+    }
+
     #[test]
     fn test_date_range_iter_days() {
         let from = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
@@ -325,6 +518,29 @@ mod tests {
         assert_eq!(stats1.net_lines, 120);
     }
 
+    #[test]
+    fn test_period_stats_add_commit() {
+        let mut stats = PeriodStats::default();
+        let diff = DiffStats {
+            additions: 10,
+            deletions: 4,
+            files_changed: 3,
+            binary_files: 1,
+            binary_bytes: 2_048,
+            ..Default::default()
+        };
+
+        stats.add_commit(&diff);
+        stats.add_commit(&diff);
+
+        assert_eq!(stats.commits, 2);
+        assert_eq!(stats.additions, 20);
+        assert_eq!(stats.net_lines, 12);
+        assert_eq!(stats.files_changed, 6);
+        assert_eq!(stats.binary_files, 2);
+        assert_eq!(stats.binary_bytes, 4_096);
+    }
+
     #[test]
     fn test_total_stats_from_periods() {
         let periods = vec![
@@ -333,6 +549,8 @@ mod tests {
                 additions: 100,
                 deletions: 20,
                 files_changed: 10,
+                binary_files: 2,
+                binary_bytes: 300,
                 ..Default::default()
             },
             PeriodStats {
@@ -345,6 +563,8 @@ mod tests {
         ];
 
         let total = TotalStats::from_periods(&periods);
+        assert_eq!(total.binary_files, 2);
+        assert_eq!(total.binary_bytes, 300);
 
         assert_eq!(total.commits, 8);
         assert_eq!(total.additions, 150);
@@ -369,6 +589,8 @@ mod tests {
         let json = serde_json::to_string(&result).unwrap();
         assert!(json.contains("\"repository\":\"test-repo\""));
         assert!(json.contains("\"from\":\"2024-01-01\""));
+        assert!(!json.contains("\"authors\""));
+        assert!(!json.contains("\"repositories\""));
     }
 
     #[test]
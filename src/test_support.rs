@@ -0,0 +1,60 @@
+//! Git repositories for unit tests
+//!
+//! Integration tests have their own builder in `tests/common`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use tempfile::TempDir;
+
+/// Diff cache directory shared by the unit tests, so they never write to the user's cache
+pub(crate) fn cache_dir() -> PathBuf {
+    static DIR: OnceLock<TempDir> = OnceLock::new();
+    DIR.get_or_init(|| TempDir::new().unwrap())
+        .path()
+        .to_path_buf()
+}
+
+/// Run git in `path`, panicking if it fails
+pub(crate) fn git(path: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(path)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Create a repository with one commit adding `README.md`
+pub(crate) fn create_test_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+
+    git(path, &["init", "-q"]);
+    git(path, &["config", "user.email", "test@example.com"]);
+    git(path, &["config", "user.name", "Test User"]);
+    std::fs::write(path.join("README.md"), "# Test\n").unwrap();
+    git(path, &["add", "."]);
+    git(path, &["commit", "-q", "-m", "Initial commit"]);
+
+    dir
+}
+
+/// Commit a file change authored by the given identity
+pub(crate) fn commit_as(path: &Path, file: &str, author: &str) {
+    std::fs::write(path.join(file), author).unwrap();
+    git(path, &["add", "."]);
+    git(path, &["commit", "-q", "-m", "Change", "--author", author]);
+}
+
+/// Commit the staged changes with the given author and committer dates
+pub(crate) fn commit_at(path: &Path, message: &str, author_date: &str, committer_date: &str) {
+    let status = Command::new("git")
+        .args(["commit", "-q", "-m", message])
+        .env("GIT_AUTHOR_DATE", author_date)
+        .env("GIT_COMMITTER_DATE", committer_date)
+        .current_dir(path)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git commit failed");
+}
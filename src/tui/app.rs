@@ -4,7 +4,7 @@
 
 use crate::error::Result;
 use crate::stats::{ActivityStats, AnalysisResult};
-use crate::tui::chart_type::ChartType;
+use crate::tui::chart_type::{AvailableCharts, ChartType};
 use crate::tui::event::{Event, EventHandler};
 use crate::tui::mvu::action::Action;
 use crate::tui::mvu::model::Model;
@@ -86,6 +86,10 @@ impl App {
                 single_metric,
                 scroll_offset: 0,
                 data_len: result.stats.len(),
+                charts: AvailableCharts {
+                    repositories: !result.repositories.is_empty(),
+                    ..AvailableCharts::from_metrics(&result.metrics)
+                },
             },
             result,
             activity_stats,
@@ -210,6 +214,19 @@ impl App {
             .collect()
     }
 
+    /// Get commits per period for each repository of a multi-repository analysis
+    #[must_use]
+    pub fn repository_commits(&self) -> Vec<(String, Vec<u32>)> {
+        self.result
+            .repositories
+            .iter()
+            .map(|repo| {
+                let commits = repo.stats.iter().map(|s| s.commits).collect();
+                (repo.name.clone(), commits)
+            })
+            .collect()
+    }
+
     #[must_use]
     pub fn chart_type(&self) -> ChartType {
         self.model.chart_type
@@ -220,6 +237,12 @@ impl App {
         self.model.single_metric
     }
 
+    /// Charts that have data for the selected metrics
+    #[must_use]
+    pub fn available_charts(&self) -> AvailableCharts {
+        self.model.charts
+    }
+
     /// Move to the next chart in single mode.
     pub fn next_chart(&mut self) {
         self.apply_action(Action::NextChart);
@@ -234,7 +257,7 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::stats::{PeriodStats, TotalStats};
+    use crate::stats::{PeriodStats, RepositoryStats, TotalStats};
     use crate::tui::chart_type::ChartType;
     use chrono::NaiveDate;
 
@@ -252,8 +275,14 @@ mod tests {
                 deletions: 20,
                 net_lines: 80,
                 files_changed: 10,
+                ..Default::default()
             }],
             total: TotalStats::default(),
+            authors: Vec::new(),
+            repositories: Vec::new(),
+            out_of_range_commits: 0,
+            warnings: Vec::new(),
+            metrics: crate::stats::MetricGroup::ALL.to_vec(),
         }
     }
 
@@ -282,6 +311,25 @@ mod tests {
         assert_eq!(data[0].deletions, 20);
     }
 
+    #[test]
+    fn test_repository_commits() {
+        let mut result = make_result();
+        let app = App::new(result.clone(), ActivityStats::default(), true);
+        assert!(app.repository_commits().is_empty());
+        assert!(!app.available_charts().repositories);
+
+        result.repositories = vec![
+            RepositoryStats::new("api".to_string(), result.stats.clone()),
+            RepositoryStats::new("web".to_string(), vec![PeriodStats::default()]),
+        ];
+        let app = App::new(result, ActivityStats::default(), true);
+        assert!(app.available_charts().repositories);
+        assert_eq!(
+            app.repository_commits(),
+            vec![("api".to_string(), vec![5]), ("web".to_string(), vec![0])]
+        );
+    }
+
     fn make_result_with_multiple_days() -> AnalysisResult {
         AnalysisResult {
             repository: "test".to_string(),
@@ -297,9 +345,15 @@ mod tests {
                     deletions: u64::from(day) * 2,
                     net_lines: i64::from(day) * 8,
                     files_changed: day,
+                    ..Default::default()
                 })
                 .collect(),
             total: TotalStats::default(),
+            authors: Vec::new(),
+            repositories: Vec::new(),
+            out_of_range_commits: 0,
+            warnings: Vec::new(),
+            metrics: crate::stats::MetricGroup::ALL.to_vec(),
         }
     }
 
@@ -1,3 +1,49 @@
+use crate::stats::MetricGroup;
+
+/// Which charts have data, depending on the selected metrics and repositories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AvailableCharts {
+    /// Additions/deletions were computed
+    pub lines: bool,
+    /// Files changed were computed
+    pub files: bool,
+    /// A per-repository breakdown is available
+    pub repositories: bool,
+}
+
+impl Default for AvailableCharts {
+    fn default() -> Self {
+        Self {
+            lines: true,
+            files: true,
+            repositories: false,
+        }
+    }
+}
+
+impl AvailableCharts {
+    /// Charts backed by the given metrics.
+    #[must_use]
+    pub fn from_metrics(metrics: &[MetricGroup]) -> Self {
+        Self {
+            lines: metrics.contains(&MetricGroup::Lines),
+            files: metrics.contains(&MetricGroup::Files),
+            repositories: false,
+        }
+    }
+
+    /// Whether a chart has data to show.
+    #[must_use]
+    pub fn contains(self, chart: ChartType) -> bool {
+        match chart {
+            ChartType::FilesChanged => self.files,
+            ChartType::AddDel => self.lines,
+            ChartType::Repositories => self.repositories,
+            ChartType::Commits | ChartType::Weekday | ChartType::Hour => true,
+        }
+    }
+}
+
 /// Chart type to display in single mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ChartType {
@@ -7,6 +53,7 @@ pub enum ChartType {
     AddDel,
     Weekday,
     Hour,
+    Repositories,
 }
 
 impl ChartType {
@@ -18,7 +65,8 @@ impl ChartType {
             Self::FilesChanged => Self::AddDel,
             Self::AddDel => Self::Weekday,
             Self::Weekday => Self::Hour,
-            Self::Hour => Self::Commits,
+            Self::Hour => Self::Repositories,
+            Self::Repositories => Self::Commits,
         }
     }
 
@@ -26,11 +74,12 @@ impl ChartType {
     #[must_use]
     pub fn prev(self) -> Self {
         match self {
-            Self::Commits => Self::Hour,
+            Self::Commits => Self::Repositories,
             Self::FilesChanged => Self::Commits,
             Self::AddDel => Self::FilesChanged,
             Self::Weekday => Self::AddDel,
             Self::Hour => Self::Weekday,
+            Self::Repositories => Self::Hour,
         }
     }
 
@@ -43,13 +92,15 @@ impl ChartType {
             Self::AddDel => "Add/Del",
             Self::Weekday => "Weekday",
             Self::Hour => "Hour",
+            Self::Repositories => "Repositories",
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ChartType;
+    use super::{AvailableCharts, ChartType};
+    use crate::stats::MetricGroup;
 
     #[test]
     fn chart_type_cycle() {
@@ -58,17 +109,25 @@ mod tests {
         assert_eq!(chart.next().next(), ChartType::AddDel);
         assert_eq!(chart.next().next().next(), ChartType::Weekday);
         assert_eq!(chart.next().next().next().next(), ChartType::Hour);
-        assert_eq!(chart.next().next().next().next().next(), ChartType::Commits);
+        assert_eq!(
+            chart.next().next().next().next().next(),
+            ChartType::Repositories
+        );
+        assert_eq!(ChartType::Repositories.next(), ChartType::Commits);
     }
 
     #[test]
     fn chart_type_prev_cycle() {
         let chart = ChartType::Commits;
-        assert_eq!(chart.prev(), ChartType::Hour);
-        assert_eq!(chart.prev().prev(), ChartType::Weekday);
-        assert_eq!(chart.prev().prev().prev(), ChartType::AddDel);
-        assert_eq!(chart.prev().prev().prev().prev(), ChartType::FilesChanged);
-        assert_eq!(chart.prev().prev().prev().prev().prev(), ChartType::Commits);
+        assert_eq!(chart.prev(), ChartType::Repositories);
+        assert_eq!(chart.prev().prev(), ChartType::Hour);
+        assert_eq!(chart.prev().prev().prev(), ChartType::Weekday);
+        assert_eq!(chart.prev().prev().prev().prev(), ChartType::AddDel);
+        assert_eq!(
+            chart.prev().prev().prev().prev().prev(),
+            ChartType::FilesChanged
+        );
+        assert_eq!(ChartType::FilesChanged.prev(), ChartType::Commits);
     }
 
     #[test]
@@ -78,6 +137,20 @@ mod tests {
         assert_eq!(ChartType::AddDel.name(), "Add/Del");
         assert_eq!(ChartType::Weekday.name(), "Weekday");
         assert_eq!(ChartType::Hour.name(), "Hour");
+        assert_eq!(ChartType::Repositories.name(), "Repositories");
+    }
+
+    #[test]
+    fn available_charts_from_metrics() {
+        let all = AvailableCharts::from_metrics(&MetricGroup::ALL);
+        assert_eq!(all, AvailableCharts::default());
+
+        let commits_only = AvailableCharts::from_metrics(&[MetricGroup::Commits]);
+        assert!(commits_only.contains(ChartType::Commits));
+        assert!(commits_only.contains(ChartType::Hour));
+        assert!(!commits_only.contains(ChartType::FilesChanged));
+        assert!(!commits_only.contains(ChartType::AddDel));
+        assert!(!commits_only.contains(ChartType::Repositories));
     }
 
     #[test]
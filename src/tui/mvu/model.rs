@@ -1,4 +1,4 @@
-use crate::tui::chart_type::ChartType;
+use crate::tui::chart_type::{AvailableCharts, ChartType};
 
 /// UI state for MVU update function.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -8,6 +8,7 @@ pub struct Model {
     pub single_metric: bool,
     pub scroll_offset: usize,
     pub data_len: usize,
+    pub charts: AvailableCharts,
 }
 
 impl Model {
@@ -11,11 +11,17 @@ pub fn update(mut model: Model, action: Action) -> Model {
         Action::NextChart => {
             if model.single_metric {
                 model.chart_type = model.chart_type.next();
+                while !model.charts.contains(model.chart_type) {
+                    model.chart_type = model.chart_type.next();
+                }
             }
         }
         Action::PrevChart => {
             if model.single_metric {
                 model.chart_type = model.chart_type.prev();
+                while !model.charts.contains(model.chart_type) {
+                    model.chart_type = model.chart_type.prev();
+                }
             }
         }
         Action::ScrollUp => {
@@ -42,7 +48,7 @@ pub fn update(mut model: Model, action: Action) -> Model {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tui::chart_type::ChartType;
+    use crate::tui::chart_type::{AvailableCharts, ChartType};
 
     fn model() -> Model {
         Model {
@@ -51,6 +57,7 @@ mod tests {
             single_metric: false,
             scroll_offset: 0,
             data_len: 5,
+            charts: AvailableCharts::default(),
         }
     }
 
@@ -88,6 +95,34 @@ mod tests {
         assert_eq!(next.chart_type, ChartType::Hour);
     }
 
+    #[test]
+    fn update_chart_cycle_skips_unavailable_charts() {
+        let mut single = model();
+        single.single_metric = true;
+        single.charts = AvailableCharts {
+            lines: false,
+            files: false,
+            repositories: false,
+        };
+
+        let next = update(single, Action::NextChart);
+        assert_eq!(next.chart_type, ChartType::Weekday);
+        let prev = update(next, Action::PrevChart);
+        assert_eq!(prev.chart_type, ChartType::Commits);
+    }
+
+    #[test]
+    fn update_chart_cycle_includes_repositories_when_available() {
+        let mut single = model();
+        single.single_metric = true;
+        single.charts.repositories = true;
+
+        let prev = update(single, Action::PrevChart);
+        assert_eq!(prev.chart_type, ChartType::Repositories);
+        let next = update(prev, Action::NextChart);
+        assert_eq!(next.chart_type, ChartType::Commits);
+    }
+
     #[test]
     fn update_scroll_up_respects_upper_bound() {
         let mut m = model();
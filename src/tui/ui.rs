@@ -5,7 +5,7 @@ use crate::tui::app::{App, Metric};
 use crate::tui::chart_type::ChartType;
 use crate::tui::widgets::{
     chart_width, render_diverging_bar_chart, render_line_chart_for_metric,
-    render_vertical_bar_chart,
+    render_stacked_repository_chart, render_vertical_bar_chart,
 };
 use ratatui::layout::Flex;
 use ratatui::prelude::*;
@@ -57,12 +57,17 @@ fn render_header(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 fn render_single_chart(frame: &mut Frame, area: Rect, app: &App) {
-    match app.chart_type() {
+    render_chart(frame, area, app, app.chart_type());
+}
+
+fn render_chart(frame: &mut Frame, area: Rect, app: &App, chart: ChartType) {
+    match chart {
         ChartType::Commits => render_line_chart_for_metric(frame, area, app, Metric::Commits),
         ChartType::FilesChanged => {
             render_line_chart_for_metric(frame, area, app, Metric::FilesChanged);
         }
         ChartType::AddDel => render_diverging_bar_chart(frame, area, app),
+        ChartType::Repositories => render_stacked_repository_chart(frame, area, app),
         ChartType::Weekday => {
             let centered = Layout::default()
                 .direction(Direction::Horizontal)
@@ -90,22 +95,38 @@ fn render_split_charts(frame: &mut Frame, area: Rect, app: &App) {
         .split(area);
 
     // Top row: Commits + Files Changed (left) | Addition/Deletions (right) - 3:1
+    // Charts without data for the selected metrics are left out
+    let charts = app.available_charts();
     let top_cols = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)])
+        .constraints(if charts.lines {
+            vec![Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)]
+        } else {
+            vec![Constraint::Ratio(1, 1)]
+        })
         .split(rows[0]);
 
-    // Left side of top row: Commits + Files Changed stacked
+    // Left side of top row: Commits, Files Changed and Commits by Repository stacked
+    let mut left = vec![ChartType::Commits];
+    if charts.files {
+        left.push(ChartType::FilesChanged);
+    }
+    if charts.repositories {
+        left.push(ChartType::Repositories);
+    }
     let top_left_rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints(vec![Constraint::Fill(1); left.len()])
         .split(top_cols[0]);
 
-    render_line_chart_for_metric(frame, top_left_rows[0], app, Metric::Commits);
-    render_line_chart_for_metric(frame, top_left_rows[1], app, Metric::FilesChanged);
+    for (chart, area) in left.into_iter().zip(top_left_rows.iter()) {
+        render_chart(frame, *area, app, chart);
+    }
 
     // Right side of top row: Addition/Deletions
-    render_diverging_bar_chart(frame, top_cols[1], app);
+    if charts.lines {
+        render_diverging_bar_chart(frame, top_cols[1], app);
+    }
 
     // Bottom row: Weekdays (1/5) | Hour (4/5)
     let bottom_cols = Layout::default()
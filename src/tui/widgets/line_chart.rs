@@ -4,7 +4,9 @@
 
 use crate::tui::app::{App, Metric};
 use ratatui::prelude::*;
-use ratatui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph};
+use ratatui::widgets::{
+    Axis, Block, Borders, Chart, Dataset, GraphType, LegendPosition, Paragraph,
+};
 
 /// Render a line chart for a specific metric
 pub fn render_line_chart_for_metric(frame: &mut Frame, area: Rect, app: &App, metric: Metric) {
@@ -74,6 +76,104 @@ pub fn render_line_chart_for_metric(frame: &mut Frame, area: Rect, app: &App, me
     frame.render_widget(chart, area);
 }
 
+/// Colors cycled through for the repositories of the stacked chart
+const REPOSITORY_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Yellow,
+    Color::Green,
+    Color::Magenta,
+    Color::Blue,
+    Color::Red,
+];
+
+/// Render commits per repository as stacked lines
+///
+/// Each repository's line is drawn on top of the ones before it, so the gap
+/// between two lines is that repository's share and the top line is the total.
+pub fn render_stacked_repository_chart(frame: &mut Frame, area: Rect, app: &App) {
+    let series = app.repository_commits();
+    let len = series.first().map_or(0, |(_, commits)| commits.len());
+
+    if len == 0 {
+        let empty = Paragraph::new("No data to display")
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .title(" Commits by Repository ")
+                    .borders(Borders::ALL),
+            );
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    // Stack each repository on the running sum of the previous ones
+    let mut running = vec![0_u32; len];
+    let stacked: Vec<(String, Vec<(f64, f64)>)> = series
+        .into_iter()
+        .map(|(name, commits)| {
+            for (sum, value) in running.iter_mut().zip(commits) {
+                *sum += value;
+            }
+            let points = running
+                .iter()
+                .enumerate()
+                .map(|(i, sum)| (i as f64, f64::from(*sum)))
+                .collect();
+            (name, points)
+        })
+        .collect();
+
+    let total: u32 = running.iter().sum();
+    let y_max = f64::from(running.iter().copied().max().unwrap_or(1)) * 1.1;
+    let title = format!(
+        " Commits by Repository (Total: {}) ",
+        format_number(i64::from(total))
+    );
+
+    let datasets = stacked
+        .iter()
+        .zip(REPOSITORY_COLORS.iter().cycle())
+        .map(|((name, points), color)| {
+            Dataset::default()
+                .name(name.clone())
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(*color))
+                .data(points)
+        })
+        .collect();
+
+    let y_labels = vec![
+        Span::raw("0"),
+        Span::raw(format_number((y_max / 2.0) as i64)),
+        Span::raw(format_number(y_max as i64)),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(title)
+                .title_style(Style::default().fg(Color::Yellow).bold())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::White)),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([0.0, (len - 1).max(1) as f64]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, y_max])
+                .labels(y_labels),
+        )
+        .legend_position(Some(LegendPosition::TopLeft))
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
+
+    frame.render_widget(chart, area);
+}
+
 fn format_number(value: i64) -> String {
     if value.abs() >= 1_000_000 {
         format!("{:.1}M", value as f64 / 1_000_000.0)
@@ -7,5 +7,5 @@ mod vertical_bar_chart;
 
 pub use diverging_bar_chart::render_diverging_bar_chart;
 pub use horizontal_bar_chart::{BarDataPoint, render_horizontal_bar_chart};
-pub use line_chart::render_line_chart_for_metric;
+pub use line_chart::{render_line_chart_for_metric, render_stacked_repository_chart};
 pub use vertical_bar_chart::{chart_width, render_vertical_bar_chart};
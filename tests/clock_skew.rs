@@ -0,0 +1,69 @@
+//! Regression test: a commit with a back-dated timestamp must not truncate the walk
+
+mod common;
+
+use chrono::{Duration, Utc};
+use common::{TestCommit, create_test_repo};
+use serde_json::Value;
+use std::path::Path;
+use std::process::Command as ProcessCommand;
+use tempfile::TempDir;
+
+/// History (oldest first): a 2020 base, a commit authored yesterday on a machine
+/// whose clock said 2001, and a recent head
+///
+/// The walk is sorted by committer date, so the skewed commit comes last, after
+/// the out-of-range base, although its author date is inside the window.
+fn create_skewed_repo() -> TempDir {
+    let yesterday = format!("{} +0000", (Utc::now() - Duration::days(1)).timestamp());
+    let now = format!("{} +0000", Utc::now().timestamp());
+
+    create_test_repo(&[
+        TestCommit::new("base.txt", "base\n").with_date("2020-01-01T12:00:00+00:00"),
+        TestCommit::new("skewed.txt", "skewed\n")
+            .with_author_date(&yesterday)
+            .with_committer_date("2001-01-01T12:00:00+00:00"),
+        TestCommit::new("head.txt", "head\n").with_date(&now),
+    ])
+}
+
+fn run_json(repo: &Path, extra: &[&str]) -> Value {
+    let output = ProcessCommand::new(env!("CARGO_BIN_EXE_kodo"))
+        .args([
+            "--repo",
+            repo.to_str().expect("repo path"),
+            "--output",
+            "json",
+            "--tz",
+            "utc",
+            "--days",
+            "7",
+            "--date-field",
+            "author",
+            "--no-cache",
+        ])
+        .args(extra)
+        .output()
+        .expect("run kodo");
+    assert!(output.status.success());
+
+    serde_json::from_slice(&output.stdout).expect("valid json stdout")
+}
+
+#[test]
+fn skewed_commit_does_not_truncate_walk() {
+    let dir = create_skewed_repo();
+
+    // The default slop walks past the single old base to the skewed commit
+    let result = run_json(dir.path(), &[]);
+    assert_eq!(result["total"]["commits"], 2);
+    assert_eq!(result["out_of_range_commits"], 1);
+
+    let result = run_json(dir.path(), &["--skew-slop", "0"]);
+    assert_eq!(result["total"]["commits"], 2);
+
+    // Stopping at the first old commit loses the skewed one
+    let result = run_json(dir.path(), &["--skew-slop", "1"]);
+    assert_eq!(result["total"]["commits"], 1);
+    assert_eq!(result["out_of_range_commits"], 1);
+}
@@ -1,2 +1,103 @@
+// Each integration test binary uses a different subset of these helpers
+#![allow(dead_code)]
+
 pub mod tui_fixture;
 pub mod tui_render;
+
+use std::path::Path;
+use std::process::Command as ProcessCommand;
+use tempfile::TempDir;
+
+/// Run git in `path`, panicking if it fails
+pub fn git(path: &Path, args: &[&str]) {
+    let status = ProcessCommand::new("git")
+        .args(args)
+        .current_dir(path)
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// A commit for [`create_test_repo`] or [`commit`]
+#[derive(Debug, Clone)]
+pub struct TestCommit {
+    files: Vec<(String, String)>,
+    message: String,
+    author_date: Option<String>,
+    committer_date: Option<String>,
+}
+
+impl TestCommit {
+    /// Commit writing one file, with the file name as message
+    pub fn new(file: &str, contents: &str) -> Self {
+        Self {
+            files: vec![(file.to_string(), contents.to_string())],
+            message: file.to_string(),
+            author_date: None,
+            committer_date: None,
+        }
+    }
+
+    /// Also write another file in the same commit
+    pub fn with_file(mut self, file: &str, contents: &str) -> Self {
+        self.files.push((file.to_string(), contents.to_string()));
+        self
+    }
+
+    /// Set both dates (any format git accepts, e.g. `2001-01-01T12:00:00+00:00`)
+    pub fn with_date(self, date: &str) -> Self {
+        self.with_author_date(date).with_committer_date(date)
+    }
+
+    /// Set the author date
+    pub fn with_author_date(mut self, date: &str) -> Self {
+        self.author_date = Some(date.to_string());
+        self
+    }
+
+    /// Set the committer date
+    pub fn with_committer_date(mut self, date: &str) -> Self {
+        self.committer_date = Some(date.to_string());
+        self
+    }
+}
+
+/// Create a repository with the given commits, oldest first
+pub fn create_test_repo(commits: &[TestCommit]) -> TempDir {
+    let dir = TempDir::new().expect("create temp dir");
+    let path = dir.path();
+
+    git(path, &["init", "-q"]);
+    git(path, &["config", "user.email", "test@example.com"]);
+    git(path, &["config", "user.name", "Test User"]);
+    for test_commit in commits {
+        commit(path, test_commit);
+    }
+
+    dir
+}
+
+/// Write the commit's files and commit them
+pub fn commit(path: &Path, test_commit: &TestCommit) {
+    for (file, contents) in &test_commit.files {
+        let file = path.join(file);
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent).expect("create directory");
+        }
+        std::fs::write(file, contents).expect("write file");
+    }
+    git(path, &["add", "."]);
+
+    let mut command = ProcessCommand::new("git");
+    command
+        .args(["commit", "-q", "-m", &test_commit.message])
+        .current_dir(path);
+    if let Some(date) = &test_commit.author_date {
+        command.env("GIT_AUTHOR_DATE", date);
+    }
+    if let Some(date) = &test_commit.committer_date {
+        command.env("GIT_COMMITTER_DATE", date);
+    }
+    let status = command.status().expect("git commit");
+    assert!(status.success(), "git commit failed");
+}
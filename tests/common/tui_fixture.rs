@@ -1,5 +1,5 @@
 use chrono::NaiveDate;
-use kodo::stats::{ActivityStats, AnalysisResult, PeriodStats};
+use kodo::stats::{ActivityStats, AnalysisResult, PeriodStats, RepositoryStats};
 use kodo::tui::App;
 
 pub fn fixed_analysis_result() -> AnalysisResult {
@@ -22,6 +22,32 @@ pub fn fixed_analysis_result() -> AnalysisResult {
     )
 }
 
+pub fn fixed_multi_repo_result() -> AnalysisResult {
+    let mut result = fixed_analysis_result();
+    let api: Vec<_> = result
+        .stats
+        .iter()
+        .map(|s| PeriodStats {
+            commits: s.commits / 2,
+            ..s.clone()
+        })
+        .collect();
+    let web: Vec<_> = result
+        .stats
+        .iter()
+        .map(|s| PeriodStats {
+            commits: s.commits - s.commits / 2,
+            ..s.clone()
+        })
+        .collect();
+    result.repository = "2 repos".to_string();
+    result.repositories = vec![
+        RepositoryStats::new("api".to_string(), api),
+        RepositoryStats::new("web".to_string(), web),
+    ];
+    result
+}
+
 pub fn fixed_activity_stats() -> ActivityStats {
     ActivityStats {
         weekday: [3, 5, 2, 4, 6, 1, 3],
@@ -61,6 +87,7 @@ fn period(
         deletions,
         net_lines: additions_i64 - deletions_i64,
         files_changed,
+        ..Default::default()
     }
 }
 
@@ -0,0 +1,95 @@
+//! The on-disk diff cache gives the same results and is managed by `kodo cache`
+
+mod common;
+
+use common::{TestCommit, create_test_repo};
+use serde_json::Value;
+use std::path::Path;
+use std::process::{Command as ProcessCommand, Output};
+use tempfile::TempDir;
+
+fn create_repo() -> TempDir {
+    create_test_repo(&[
+        TestCommit::new("a.txt", "a\n"),
+        TestCommit::new("b.txt", "b\nb\n"),
+    ])
+}
+
+fn run(cache_dir: &Path, args: &[&str]) -> Output {
+    ProcessCommand::new(env!("CARGO_BIN_EXE_kodo"))
+        .args(args)
+        .env("KODO_CACHE_DIR", cache_dir)
+        .output()
+        .expect("run kodo")
+}
+
+fn kodo(cache_dir: &Path, args: &[&str]) -> String {
+    let output = run(cache_dir, args);
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).expect("utf8 stdout")
+}
+#[test]
+fn cached_run_matches_uncached_run() {
+    let repo = create_repo();
+    let cache = TempDir::new().expect("create cache dir");
+    let analyze = [
+        "--repo",
+        repo.path().to_str().expect("repo path"),
+        "--output",
+        "json",
+    ];
+
+    let uncached: Value = serde_json::from_str(&kodo(
+        cache.path(),
+        &[&analyze[..], &["--no-cache"]].concat(),
+    ))
+    .expect("valid json");
+    assert!(kodo(cache.path(), &["cache", "stats"]).contains("Commits: 0"));
+
+    for _ in 0..2 {
+        let cached: Value =
+            serde_json::from_str(&kodo(cache.path(), &analyze)).expect("valid json");
+        assert_eq!(cached["total"], uncached["total"]);
+    }
+    let stats = kodo(cache.path(), &["cache", "stats"]);
+    assert!(stats.contains("Repositories: 1"));
+    assert!(stats.contains("Commits: 2"));
+
+    kodo(cache.path(), &["cache", "clear"]);
+    assert!(kodo(cache.path(), &["cache", "stats"]).contains("Commits: 0"));
+}
+
+#[test]
+fn unwritable_cache_only_warns() {
+    let repo = create_repo();
+    let dir = TempDir::new().expect("create temp dir");
+    // A file where the cache directory should be
+    let blocked = dir.path().join("blocked");
+    std::fs::write(&blocked, "").expect("write file");
+
+    let output = run(
+        &blocked,
+        &[
+            "--repo",
+            repo.path().to_str().expect("repo path"),
+            "--output",
+            "json",
+        ],
+    );
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Could not save diff cache"));
+
+    let result: Value = serde_json::from_slice(&output.stdout).expect("valid json stdout");
+    assert_eq!(result["total"]["commits"], 2);
+    let warnings = result["warnings"].as_array().expect("warnings array");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["skipped"], false);
+    assert!(
+        warnings[0]["error"]
+            .as_str()
+            .expect("error message")
+            .starts_with("Could not save diff cache")
+    );
+}
@@ -0,0 +1,153 @@
+//! Options are layered: built-in defaults, config defaults, repository entry, env, CLI
+
+mod common;
+
+use common::{TestCommit, create_test_repo};
+use serde_json::{Value, json};
+use std::path::Path;
+use std::process::{Command as ProcessCommand, Output};
+use tempfile::TempDir;
+
+fn create_repo() -> TempDir {
+    create_test_repo(&[
+        TestCommit::new("notes.txt", "a\nb\nc\n").with_file("main.rs", "fn main() {}\n")
+    ])
+}
+
+fn write_config(dir: &Path, repo: &Path) -> std::path::PathBuf {
+    let config = dir.join("config.json");
+    let contents = json!({
+        "repositories": [{ "name": "api", "path": repo, "ext": ["rs"] }],
+        "defaults": {
+            "days": 30,
+            "period": "weekly",
+            "output": "json",
+            "include_generated": true,
+            "author": ["nobody@example.com"],
+        },
+    });
+    std::fs::write(&config, contents.to_string()).expect("write config");
+    config
+}
+
+fn run(config: &Path, env: &[(&str, &str)], extra: &[&str]) -> Output {
+    let mut command = ProcessCommand::new(env!("CARGO_BIN_EXE_kodo"));
+    command
+        .args(["--config", config.to_str().expect("config path")])
+        .arg("--no-cache")
+        .args(extra)
+        .env_remove("KODO_PERIOD")
+        .env_remove("KODO_OUTPUT")
+        .env_remove("KODO_DAYS")
+        .env_remove("KODO_AUTHOR")
+        .env_remove("KODO_INCLUDE_GENERATED");
+    for (key, value) in env {
+        command.env(key, value);
+    }
+    command.output().expect("run kodo")
+}
+
+fn analyze(config: &Path, env: &[(&str, &str)], extra: &[&str]) -> Value {
+    let output = run(config, env, extra);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("valid json stdout")
+}
+
+#[test]
+fn config_env_and_cli_layers_apply_in_order() {
+    let repo = create_repo();
+    let config_dir = TempDir::new().expect("create config dir");
+    let config = write_config(config_dir.path(), repo.path());
+
+    // Config defaults select JSON output, weekly periods and an author nobody matches
+    let result = analyze(&config, &[], &[]);
+    assert_eq!(result["period"], "weekly");
+    assert_eq!(result["total"]["commits"], 0);
+
+    // The repository keeps only .rs files
+    let result = analyze(&config, &[("KODO_AUTHOR", "test@example.com")], &[]);
+    assert_eq!(result["total"]["commits"], 1);
+    assert_eq!(result["total"]["additions"], 1);
+
+    let result = analyze(
+        &config,
+        &[("KODO_PERIOD", "monthly")],
+        &["--author", "test@example.com"],
+    );
+    assert_eq!(result["period"], "monthly");
+
+    let result = analyze(
+        &config,
+        &[("KODO_PERIOD", "monthly")],
+        &[
+            "--period",
+            "daily",
+            "--ext",
+            "txt",
+            "--author",
+            "test@example.com",
+        ],
+    );
+    assert_eq!(result["period"], "daily");
+    assert_eq!(result["total"]["additions"], 3);
+}
+
+#[test]
+fn config_show_resolved_names_each_source() {
+    let repo = create_repo();
+    let config_dir = TempDir::new().expect("create config dir");
+    let config = write_config(config_dir.path(), repo.path());
+
+    let output = run(
+        &config,
+        &[("KODO_PERIOD", "monthly")],
+        &["--tz", "utc", "config", "show", "--resolved"],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = |option: &str| {
+        stdout
+            .lines()
+            .find(|line| line.split_whitespace().next() == Some(option))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+    };
+
+    assert_eq!(line("days").as_deref(), Some("days 30 (config)"));
+    assert_eq!(
+        line("period").as_deref(),
+        Some("period monthly (env KODO_PERIOD)")
+    );
+    assert_eq!(line("tz").as_deref(), Some("tz utc (cli)"));
+    assert_eq!(line("skew_slop").as_deref(), Some("skew_slop 5 (default)"));
+    assert_eq!(line("ext").as_deref(), Some("ext (all) (default)"));
+    assert_eq!(
+        line("include_generated").as_deref(),
+        Some("include_generated true (config)")
+    );
+    assert!(stdout.contains("Repository: api\n  ext  rs  (repository)\n"));
+}
+
+#[test]
+fn cli_turns_off_flags_set_in_config() {
+    let repo = create_repo();
+    let config_dir = TempDir::new().expect("create config dir");
+    let config = write_config(config_dir.path(), repo.path());
+
+    let output = run(
+        &config,
+        &[("KODO_INCLUDE_GENERATED", "true")],
+        &["--include-generated=false", "config", "show", "--resolved"],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.split_whitespace().collect::<Vec<_>>()
+                == ["include_generated", "false", "(cli)"])
+    );
+}
@@ -1,51 +1,17 @@
+mod common;
+
+use common::{TestCommit, create_test_repo};
 use serde_json::Value;
 use std::process::Command as ProcessCommand;
 use tempfile::TempDir;
 
-fn create_test_repo() -> TempDir {
-    let dir = TempDir::new().expect("create temp dir");
-    let path = dir.path();
-
-    ProcessCommand::new("git")
-        .args(["init"])
-        .current_dir(path)
-        .output()
-        .expect("git init");
-
-    ProcessCommand::new("git")
-        .args(["config", "user.email", "test@example.com"])
-        .current_dir(path)
-        .output()
-        .expect("git config email");
-
-    ProcessCommand::new("git")
-        .args(["config", "user.name", "Test User"])
-        .current_dir(path)
-        .output()
-        .expect("git config name");
-
-    std::fs::write(path.join("README.md"), "# Test\n").expect("write file");
-
-    ProcessCommand::new("git")
-        .args(["add", "."])
-        .current_dir(path)
-        .output()
-        .expect("git add");
-
-    ProcessCommand::new("git")
-        .args(["commit", "-m", "Initial commit"])
-        .current_dir(path)
-        .output()
-        .expect("git commit");
-
-    dir
-}
-
 #[test]
 fn json_output_does_not_include_spinner_text() {
-    let dir = create_test_repo();
+    let dir = create_test_repo(&[TestCommit::new("README.md", "# Test\n")]);
+    let cache_dir = TempDir::new().expect("create cache dir");
 
     let output = ProcessCommand::new(env!("CARGO_BIN_EXE_kodo"))
+        .env("KODO_CACHE_DIR", cache_dir.path())
         .args([
             "--repo",
             dir.path().to_str().expect("repo path"),
@@ -72,9 +38,11 @@ fn json_output_does_not_include_spinner_text() {
 
 #[test]
 fn csv_output_does_not_include_spinner_text() {
-    let dir = create_test_repo();
+    let dir = create_test_repo(&[TestCommit::new("README.md", "# Test\n")]);
+    let cache_dir = TempDir::new().expect("create cache dir");
 
     let output = ProcessCommand::new(env!("CARGO_BIN_EXE_kodo"))
+        .env("KODO_CACHE_DIR", cache_dir.path())
         .args([
             "--repo",
             dir.path().to_str().expect("repo path"),
@@ -0,0 +1,52 @@
+//! A failing repository is skipped with a warning unless `--strict` is given
+
+mod common;
+
+use common::{TestCommit, create_test_repo};
+use serde_json::{Value, json};
+use std::path::Path;
+use std::process::{Command as ProcessCommand, Output};
+use tempfile::TempDir;
+
+fn run(config: &Path, extra: &[&str]) -> Output {
+    ProcessCommand::new(env!("CARGO_BIN_EXE_kodo"))
+        .args(["--config", config.to_str().expect("config path")])
+        .args(["--output", "json", "--no-cache"])
+        .args(extra)
+        .output()
+        .expect("run kodo")
+}
+
+#[test]
+fn missing_repository_is_reported_and_skipped() {
+    let repo = create_test_repo(&[TestCommit::new("a.txt", "a\n")]);
+    let gone = TempDir::new().expect("create temp dir");
+    let config_dir = TempDir::new().expect("create config dir");
+    let config = config_dir.path().join("config.json");
+    let repositories = json!([
+        { "name": "api", "path": repo.path() },
+        { "name": "gone", "path": gone.path().join("moved") },
+    ]);
+    std::fs::write(&config, json!({ "repositories": repositories }).to_string())
+        .expect("write config");
+
+    let output = run(&config, &[]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("warning: skipped repository 'gone': Repository not found"));
+
+    let result: Value = serde_json::from_slice(&output.stdout).expect("valid json stdout");
+    assert_eq!(result["repository"], "api");
+    assert_eq!(result["total"]["commits"], 1);
+    assert_eq!(result["warnings"][0]["repository"], "gone");
+    assert!(
+        result["warnings"][0]["error"]
+            .as_str()
+            .expect("error message")
+            .starts_with("Repository not found")
+    );
+
+    let output = run(&config, &["--strict"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
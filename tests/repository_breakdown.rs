@@ -0,0 +1,54 @@
+//! Multi-repository runs report each repository alongside the combined series
+
+mod common;
+
+use common::{TestCommit, create_test_repo};
+use serde_json::{Value, json};
+use std::path::Path;
+use std::process::Command as ProcessCommand;
+use tempfile::TempDir;
+
+fn run_json(config: &Path) -> Value {
+    let output = ProcessCommand::new(env!("CARGO_BIN_EXE_kodo"))
+        .args(["--config", config.to_str().expect("config path")])
+        .args(["--output", "json", "--tz", "utc", "--no-cache"])
+        .output()
+        .expect("run kodo");
+    assert!(output.status.success());
+
+    serde_json::from_slice(&output.stdout).expect("valid json stdout")
+}
+
+#[test]
+fn json_reports_each_repository() {
+    let api = create_test_repo(&[
+        TestCommit::new("a.txt", "a.txt\n"),
+        TestCommit::new("b.txt", "b.txt\n"),
+    ]);
+    let web = create_test_repo(&[TestCommit::new("c.txt", "c.txt\n")]);
+    let config_dir = TempDir::new().expect("create config dir");
+    let config = config_dir.path().join("config.json");
+    let repositories = json!([
+        { "name": "api", "path": api.path() },
+        { "name": "web", "path": web.path() },
+    ]);
+    std::fs::write(&config, json!({ "repositories": repositories }).to_string())
+        .expect("write config");
+
+    let result = run_json(&config);
+    assert_eq!(result["repository"], "2 repos");
+    assert_eq!(result["total"]["commits"], 3);
+
+    let repos = result["repositories"].as_array().expect("repositories");
+    assert_eq!(repos.len(), 2);
+    assert_eq!(repos[0]["name"], "api");
+    assert_eq!(repos[0]["total"]["commits"], 2);
+    assert_eq!(repos[1]["name"], "web");
+    assert_eq!(repos[1]["total"]["commits"], 1);
+    for repo in repos {
+        assert_eq!(
+            repo["stats"].as_array().map(Vec::len),
+            result["stats"].as_array().map(Vec::len)
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Path filters rooted at the parent repository apply inside its submodules
+
+mod common;
+
+use common::{TestCommit, create_test_repo, git};
+use serde_json::Value;
+use std::path::Path;
+use std::process::Command as ProcessCommand;
+use tempfile::TempDir;
+
+/// A parent repository with a submodule checked out at `libs/foo`
+fn create_parent_with_submodule() -> (TempDir, TempDir) {
+    let sub = create_test_repo(&[
+        TestCommit::new("src/lib.rs", "a\nb\n").with_file("docs/notes.md", "c\n"),
+        TestCommit::new("docs/more.md", "d\ne\nf\n"),
+    ]);
+    let parent = create_test_repo(&[TestCommit::new("README.md", "# Parent\n")]);
+    git(
+        parent.path(),
+        &[
+            "-c",
+            "protocol.file.allow=always",
+            "submodule",
+            "add",
+            "-q",
+            sub.path().to_str().expect("submodule path"),
+            "libs/foo",
+        ],
+    );
+    git(parent.path(), &["commit", "-q", "-m", "Add submodule"]);
+
+    (parent, sub)
+}
+
+fn run_json(repo: &Path, args: &[&str]) -> Value {
+    let output = ProcessCommand::new(env!("CARGO_BIN_EXE_kodo"))
+        .args(["--repo", repo.to_str().expect("repo path")])
+        .args(["--output", "json", "--tz", "utc", "--no-cache"])
+        .arg("--recurse-submodules")
+        .args(args)
+        .output()
+        .expect("run kodo");
+    assert!(output.status.success());
+
+    serde_json::from_slice(&output.stdout).expect("valid json stdout")
+}
+
+/// Totals of the submodule, if it was analyzed
+///
+/// Single-repository runs have no `repositories` breakdown.
+fn submodule_total(result: &Value) -> Option<&Value> {
+    result["repositories"]
+        .as_array()?
+        .iter()
+        .find(|repo| {
+            repo["name"]
+                .as_str()
+                .is_some_and(|n| n.ends_with("/libs/foo"))
+        })
+        .map(|repo| &repo["total"])
+}
+
+#[test]
+fn include_globs_are_rerooted_onto_submodules() {
+    let (parent, _sub) = create_parent_with_submodule();
+
+    let result = run_json(parent.path(), &[]);
+    let total = submodule_total(&result).expect("submodule analyzed");
+    assert_eq!(total["commits"], 2);
+    assert_eq!(total["additions"], 6);
+
+    let result = run_json(parent.path(), &["--include", "libs/foo/src/**"]);
+    let total = submodule_total(&result).expect("submodule analyzed");
+    assert_eq!(total["additions"], 2);
+
+    let result = run_json(parent.path(), &["--exclude", "/libs/foo/docs"]);
+    let total = submodule_total(&result).expect("submodule analyzed");
+    assert_eq!(total["additions"], 2);
+
+    // An include list that reaches nothing inside the submodule skips it
+    let result = run_json(parent.path(), &["--include", "/README.md"]);
+    assert!(submodule_total(&result).is_none());
+}
+
+#[test]
+fn pathspec_is_rerooted_onto_submodules() {
+    let (parent, _sub) = create_parent_with_submodule();
+
+    let result = run_json(parent.path(), &["--", "libs/foo"]);
+    let total = submodule_total(&result).expect("submodule analyzed");
+    assert_eq!(total["commits"], 2);
+    assert_eq!(total["additions"], 6);
+
+    let result = run_json(parent.path(), &["--", "libs/foo/docs"]);
+    let total = submodule_total(&result).expect("submodule analyzed");
+    assert_eq!(total["commits"], 2);
+    assert_eq!(total["additions"], 4);
+
+    let result = run_json(parent.path(), &["--", "README.md"]);
+    assert!(submodule_total(&result).is_none());
+}
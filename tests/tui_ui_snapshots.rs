@@ -1,8 +1,12 @@
 mod common;
 
-use common::tui_fixture::make_app;
+use common::tui_fixture::{
+    fixed_activity_stats, fixed_analysis_result, fixed_multi_repo_result, make_app,
+};
 use common::tui_render::render_ui;
 use insta::assert_snapshot;
+use kodo::stats::MetricGroup;
+use kodo::tui::App;
 
 #[test]
 fn test_ui_split_default_snapshot() {
@@ -46,3 +50,29 @@ fn test_ui_single_weekday_snapshot() {
 fn test_ui_single_hour_snapshot() {
     assert_single_chart_snapshot("ui_single_hour", 4);
 }
+
+#[test]
+fn test_ui_split_commits_only_snapshot() {
+    let mut result = fixed_analysis_result();
+    result.metrics = vec![MetricGroup::Commits];
+    let app = App::new(result, fixed_activity_stats(), false);
+    let rendered = render_ui(&app);
+    assert_snapshot!("ui_split_commits_only", rendered);
+}
+
+#[test]
+fn test_ui_split_repositories_snapshot() {
+    let app = App::new(fixed_multi_repo_result(), fixed_activity_stats(), false);
+    let rendered = render_ui(&app);
+    assert_snapshot!("ui_split_repositories", rendered);
+}
+
+#[test]
+fn test_ui_single_repositories_snapshot() {
+    let mut app = App::new(fixed_multi_repo_result(), fixed_activity_stats(), true);
+    for _ in 0..5 {
+        app.next_chart();
+    }
+    let rendered = render_ui(&app);
+    assert_snapshot!("ui_single_repositories", rendered);
+}